@@ -0,0 +1,197 @@
+//! Derive macros for `actix-inertia`. Use them through the `macros` feature of that crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, LitStr};
+
+/// Implements `InertiaPage` and `actix_web::Responder` for a props struct.
+///
+/// ```ignore
+/// #[derive(Serialize, InertiaPage)]
+/// #[inertia(component = "Users/Show")]
+/// struct ShowUser {
+///     user: User,
+///     #[inertia(lazy)]
+///     activity: Vec<Event>,
+///     #[inertia(always)]
+///     flash: Option<String>,
+///     #[inertia(deferred = "sidebar")]
+///     teams: Vec<Team>,
+///     #[inertia(merge)]
+///     comments: Vec<Comment>,
+/// }
+/// ```
+///
+/// Prop keys follow `#[serde(rename)]` and `#[serde(rename_all)]`.
+///
+/// Lazy and deferred fields are only left out of the response: they are computed when the
+/// struct is built and serialized with it on every request. Build expensive props with
+/// `Inertia::lazy_with` or `Inertia::defer_with` instead.
+#[proc_macro_derive(InertiaPage, attributes(inertia))]
+pub fn derive_inertia_page(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let component = container_component(&input.attrs)?.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ident,
+            "missing #[inertia(component = \"...\")] on the props struct",
+        )
+    })?;
+    let rename_all = serde_rename_all(&input.attrs)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "InertiaPage can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "InertiaPage can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut options = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let key = match serde_rename(&field.attrs)? {
+            Some(key) => key,
+            None => rename(
+                ident.to_string().trim_start_matches("r#"),
+                rename_all.as_deref(),
+            ),
+        };
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("inertia")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("lazy") {
+                    options.push(quote!(.lazy(#key)));
+                } else if meta.path.is_ident("always") {
+                    options.push(quote!(.always(#key)));
+                } else if meta.path.is_ident("merge") {
+                    options.push(quote!(.merge(#key)));
+                } else if meta.path.is_ident("deferred") {
+                    let group = if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<LitStr>()?.value()
+                    } else {
+                        "default".to_string()
+                    };
+                    options.push(quote!(.defer(#key, #group)));
+                } else {
+                    return Err(
+                        meta.error("expected one of `lazy`, `always`, `deferred` or `merge`")
+                    );
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::actix_inertia::InertiaPage for #name #ty_generics #where_clause {
+            const COMPONENT: &'static str = #component;
+
+            fn prop_options() -> ::actix_inertia::PropOptions {
+                ::actix_inertia::PropOptions::new() #(#options)*
+            }
+        }
+
+        impl #impl_generics ::actix_inertia::__private::actix_web::Responder for #name #ty_generics #where_clause {
+            type Body = ::actix_inertia::__private::actix_web::body::BoxBody;
+
+            fn respond_to(
+                self,
+                req: &::actix_inertia::__private::actix_web::HttpRequest,
+            ) -> ::actix_inertia::__private::actix_web::HttpResponse<Self::Body> {
+                ::actix_inertia::__private::actix_web::Responder::respond_to(
+                    ::actix_inertia::InertiaPage::into_inertia(self, req),
+                    req,
+                )
+            }
+        }
+    })
+}
+
+fn container_component(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    let mut component = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("inertia")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("component") {
+                component = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error("expected `component = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(component)
+}
+
+fn serde_rename(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    serde_string(attrs, "rename")
+}
+
+fn serde_rename_all(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    serde_string(attrs, "rename_all")
+}
+
+/// The string value of `#[serde(name = "...")]`, skipping every other serde option.
+fn serde_string(attrs: &[Attribute], name: &str) -> syn::Result<Option<String>> {
+    let mut value = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) && meta.input.peek(syn::Token![=]) {
+                value = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(value)
+}
+
+/// Applies a serde `rename_all` rule to a snake_case field name.
+fn rename(field: &str, rule: Option<&str>) -> String {
+    let words = field.split('_').filter(|w| !w.is_empty());
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+            None => String::new(),
+        }
+    };
+
+    match rule {
+        Some("lowercase") => field.to_lowercase(),
+        Some("UPPERCASE") => field.to_uppercase(),
+        Some("PascalCase") => words.map(capitalize).collect(),
+        Some("camelCase") => words
+            .enumerate()
+            .map(|(i, w)| if i == 0 { w.to_string() } else { capitalize(w) })
+            .collect(),
+        Some("SCREAMING_SNAKE_CASE") => field.to_uppercase(),
+        Some("kebab-case") => field.replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => field.replace('_', "-").to_uppercase(),
+        _ => field.to_string(),
+    }
+}
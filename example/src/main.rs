@@ -1,4 +1,4 @@
-use actix_inertia::{inertia_responder::InertiaResponder, VersionMiddleware};
+use actix_inertia::{inertia_responder::InertiaResponder, ResponseFactory, VersionMiddleware};
 use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 use serde_json::json;
 use serde_json::Value;
@@ -69,6 +69,7 @@ fn response_with_html(req: &HttpRequest, props: HelloProps, component: String) -
 async fn main() -> std::io::Result<()> {
     HttpServer::new(|| {
         App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
             .route("/", web::get().to(hello))
             .route("/hello", web::get().to(hello))
             .route("/world", web::get().to(world))
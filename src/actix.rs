@@ -1,236 +1,150 @@
-use crate::{
-    Inertia, X_INERTIA, X_INERTIA_ERROR_BAG, X_INERTIA_LOCATION,
-    X_INERTIA_PARTIAL_COMPONENT, X_INERTIA_PARTIAL_EXCEPT, X_INERTIA_PARTIAL_ONLY,
-    X_INERTIA_VERSION,
-};
+//! The actix-web adapter: responders, extractors and middleware over the
+//! [`protocol`](crate::protocol) core.
+
+use crate::error::InertiaError;
+use crate::layer::{SharedProps, SkipVersionCheck};
+use crate::protocol::{is_prefetch_purpose, InertiaRequest, Rendered};
+use crate::root_view::SelectedRootView;
+use crate::validation::{flashed_errors, forget_flashed_errors};
+use crate::{Inertia, PURPOSE, SEC_PURPOSE, X_INERTIA, X_INERTIA_LOCATION, X_INERTIA_VERSION};
 use actix_service::{forward_ready, Service, Transform};
-use actix_web::body::EitherBody;
+use actix_web::body::{BoxBody, EitherBody};
 use actix_web::web;
 use actix_web::{
     dev::ServiceRequest, dev::ServiceResponse, http, Error, HttpRequest, HttpResponse, Result,
 };
+use actix_web::{HttpMessage, Responder};
 use futures::future::{ok, Ready};
 use futures_util::future::LocalBoxFuture;
-use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
-use std::fs;
-
-#[derive(Serialize, Deserialize, Clone)]
-pub struct InertiaResponse<T> {
-    component: String,
-    props: T,
-    url: String,
-    version: Option<String>,
-}
+use serde::Serialize;
+use std::sync::Arc;
 
-#[derive(Serialize)]
-pub struct HtmlResponseContext {
-    data_page: String,
-}
-
-#[derive(Clone)]
-pub struct ResponseFactory {
-    root_view: String,
-    shared_props: Arc<Mutex<serde_json::Value>>,
-    version: Option<Arc<dyn Fn() -> String + Send + Sync>>,
-}
+pub use crate::factory::ResponseFactory;
+pub use crate::protocol::{HtmlResponseContext, InertiaResponse};
 
 impl ResponseFactory {
-    pub fn new() -> Self {
-        Self {
-            root_view: "app".to_string(),
-            shared_props: Arc::new(Mutex::new(serde_json::Value::Object(Default::default()))),
-            version: None,
-        }
+    /// The factory registered for `req` as `web::Data<ResponseFactory>`. A factory added to a
+    /// scope with `app_data` takes precedence over the app's, so scopes can use their own.
+    pub fn find(req: &HttpRequest) -> Option<&ResponseFactory> {
+        req.app_data::<web::Data<ResponseFactory>>()
+            .map(|factory| factory.get_ref())
     }
 
-    pub fn set_root_view(&mut self, name: &str) {
-        self.root_view = name.to_string();
-    }
-
-    pub fn render_root(&self, data_page: &str) -> Option<String> {
-        if let Ok(mut html) = fs::read_to_string(&self.root_view) {
-            let escaped = data_page.replace("\"", "&quot;");
-            html = html.replace("{{DATA_PAGE}}", &escaped);
-            Some(html)
-        } else {
-            None
-        }
-    }
-
-    pub fn share(&self, key: &str, value: serde_json::Value) {
-        let mut shared_props = self.shared_props.lock().unwrap();
-        shared_props[key] = value;
+    pub fn location(&self, url: &str) -> HttpResponse {
+        conflict(url)
     }
+}
 
-    pub fn get_shared(&self, key: Option<&str>) -> serde_json::Value {
-        let shared_props = self.shared_props.lock().unwrap();
-        match key {
-            Some(k) => shared_props
-                .get(k)
-                .cloned()
-                .unwrap_or(serde_json::Value::Null),
-            None => shared_props.clone(),
-        }
-    }
+/// `409 Conflict` asking the client for a full page load of `url`.
+pub(crate) fn conflict(url: &str) -> HttpResponse {
+    HttpResponse::Conflict()
+        .append_header((X_INERTIA_LOCATION, url))
+        .append_header((http::header::VARY, X_INERTIA))
+        .finish()
+}
 
-    pub fn flush_shared(&self) {
-        let mut shared_props = self.shared_props.lock().unwrap();
-        *shared_props = serde_json::Value::Object(Default::default());
+/// The protocol view of an actix request, including the settings of the scope it runs in.
+pub(crate) fn inertia_request(req: &HttpRequest) -> InertiaRequest {
+    // actix-web 4 still uses the `http` 0.2 types.
+    let method = ::http::Method::from_bytes(req.method().as_str().as_bytes()).unwrap_or_default();
+    let mut request = InertiaRequest::from_headers(method, req.uri().to_string(), |name| {
+        req.headers().get(name).and_then(|v| v.to_str().ok())
+    });
+
+    request.errors = flashed_errors(req);
+    let extensions = req.extensions();
+    if let Some(SharedProps(shared)) = extensions.get::<SharedProps>() {
+        request.shared_props = shared.clone();
     }
+    request.encrypt_history = extensions.contains::<EncryptHistory>();
+    request.root_view = extensions
+        .get::<SelectedRootView>()
+        .map(|selected| selected.0.clone());
+    request.skip_version_check = req.app_data::<SkipVersionCheck>().is_some();
+    request
+}
 
-    pub fn set_version<F>(&mut self, version: F)
-    where
-        F: Fn() -> String + Send + Sync + 'static,
-    {
-        self.version = Some(Arc::new(version));
-    }
+/// Whether `req` prefetches a page the user may never see, so the handler can skip side
+/// effects such as view counters.
+pub fn is_prefetch(req: &HttpRequest) -> bool {
+    [PURPOSE, SEC_PURPOSE].into_iter().any(|name| {
+        req.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(is_prefetch_purpose)
+    })
+}
 
-    pub fn get_version(&self) -> String {
-        match &self.version {
-            Some(version_fn) => version_fn(),
-            None => "".to_string(),
-        }
+impl<T: Serialize> Inertia<T> {
+    /// Renders the page, answering `500 Internal Server Error` if that fails.
+    pub async fn into_response(self, req: &HttpRequest) -> HttpResponse {
+        self.render_response(req)
     }
 
-    pub fn render<T: Serialize>(
-        &self,
-        component: &str,
-        props: T,
-        url: &str,
-    ) -> Inertia<serde_json::Value> {
-        let shared_props = self.get_shared(None);
-        let mut props = serde_json::to_value(props).unwrap();
-        if let serde_json::Value::Object(ref mut p) = props {
-            if let serde_json::Value::Object(ref s) = shared_props {
-                p.extend(s.clone());
-            }
-        }
-        Inertia::new(component.to_string(), props, url.to_string())
+    pub async fn try_into_response(self, req: &HttpRequest) -> Result<HttpResponse, InertiaError> {
+        self.try_render_response(req)
     }
 
-    pub fn location(&self, url: &str) -> HttpResponse {
-        HttpResponse::Conflict()
-            .append_header((X_INERTIA_LOCATION, url))
-            .finish()
+    pub(crate) fn render_response(self, req: &HttpRequest) -> HttpResponse {
+        let component = self.component.clone();
+        self.try_render_response(req).unwrap_or_else(|err| {
+            report!(
+                error,
+                "failed to render Inertia component `{component}`: {err}"
+            );
+            HttpResponse::from_error(err)
+        })
     }
-}
 
-impl<T: Serialize> Inertia<T> {
-    pub fn new(component: String, props: T, url: String) -> Self {
-        Self {
-            component,
-            props,
-            url: Some(url),
+    fn try_render_response(self, req: &HttpRequest) -> Result<HttpResponse, InertiaError> {
+        let factory = ResponseFactory::find(req);
+        if factory.is_none() && cfg!(debug_assertions) {
+            return Err(InertiaError::MissingFactory);
         }
-    }
-
-    pub async fn into_response(self, req: &HttpRequest) -> HttpResponse {
-        let version = req
-            .app_data::<web::Data<ResponseFactory>>()
-            .and_then(|factory| {
-                let v = factory.get_version();
-                if v.is_empty() {
-                    None
-                } else {
-                    Some(v)
-                }
-            });
-
-        let mut props_value = serde_json::to_value(self.props).unwrap_or_else(|_| serde_json::Value::Null);
-
-        if let serde_json::Value::Object(ref mut map) = props_value {
-            let partial_component = req
-                .headers()
-                .get(X_INERTIA_PARTIAL_COMPONENT)
-                .and_then(|v| v.to_str().ok());
-            let partial_only = req
-                .headers()
-                .get(X_INERTIA_PARTIAL_ONLY)
-                .and_then(|v| v.to_str().ok());
-            let partial_except = req
-                .headers()
-                .get(X_INERTIA_PARTIAL_EXCEPT)
-                .and_then(|v| v.to_str().ok());
-
-            let should_filter = match partial_component {
-                Some(comp) => comp == self.component,
-                None => partial_only.is_some() || partial_except.is_some(),
-            };
-
-            if should_filter {
-                if let Some(data) = partial_only {
-                    let keys: Vec<&str> = data.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()).collect();
-                    map.retain(|k, _| keys.iter().any(|key| key == &k.as_str()));
-                }
 
-                if let Some(excepts) = partial_except {
-                    for key in excepts.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
-                        map.remove(key);
-                    }
-                }
-            }
+        let request = inertia_request(req);
+        let (rendered, diagnostics) = self.render_page_with_diagnostics(&request, factory)?;
+        let mut headers = rendered.headers(&request, factory);
+        if let Some(diagnostics) = diagnostics {
+            headers.extend(diagnostics.headers(&request));
         }
-
-        let inertia_response = InertiaResponse {
-            component: self.component,
-            props: props_value,
-            url: self.url.unwrap_or_else(|| req.uri().to_string()),
-            version,
-        };
-
-        if req.headers().contains_key(X_INERTIA) {
-            let mut response = HttpResponse::Ok()
+        let forget_errors = rendered.forgets_errors(&request);
+        let mut response = match rendered {
+            Rendered::Json(page) => HttpResponse::Ok()
                 .content_type("application/json")
-                .append_header((X_INERTIA, "true"))
-                .json(inertia_response);
-
-            if let Some(error_bag) = req.headers().get(X_INERTIA_ERROR_BAG) {
-                response.headers_mut().append(
-                    http::header::HeaderName::from_static("x-inertia-error-bag"),
-                    error_bag.clone(),
-                );
-            }
-
-            if let Some(partial_component) = req.headers().get(X_INERTIA_PARTIAL_COMPONENT) {
-                response.headers_mut().append(
-                    http::header::HeaderName::from_static("x-inertia-partial-component"),
-                    partial_component.clone(),
-                );
-            }
+                .body(page),
+            Rendered::Html(html) => HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(html),
+            Rendered::NoRootView(page) => HttpResponse::Ok()
+                .content_type("application/json")
+                .body(page),
+            Rendered::Conflict(_) => HttpResponse::Conflict().finish(),
+            Rendered::NotModified(_) => HttpResponse::NotModified().finish(),
+        };
 
-            if let Some(partial_only) = req.headers().get(X_INERTIA_PARTIAL_ONLY) {
+        for (name, value) in headers {
+            if let Ok(value) = http::header::HeaderValue::from_str(&value) {
                 response.headers_mut().append(
-                    http::header::HeaderName::from_static("x-inertia-partial-data"),
-                    partial_only.clone(),
+                    http::header::HeaderName::from_bytes(name.as_bytes())
+                        .expect("valid header name"),
+                    value,
                 );
             }
+        }
+        if forget_errors {
+            forget_flashed_errors(&mut response);
+        }
 
-            if let Some(partial_except) = req.headers().get(X_INERTIA_PARTIAL_EXCEPT) {
-                response.headers_mut().append(
-                    http::header::HeaderName::from_static("x-inertia-partial-except"),
-                    partial_except.clone(),
-                );
-            }
+        Ok(response)
+    }
+}
 
-            response
-        } else {
-            let ctx = HtmlResponseContext {
-                data_page: serde_json::to_string(&inertia_response).unwrap(), // Handle error in real scenario
-            };
-
-            if let Some(factory) = req.app_data::<web::Data<ResponseFactory>>() {
-                if let Some(html) = factory.render_root(&ctx.data_page) {
-                    return HttpResponse::Ok()
-                        .content_type("text/html; charset=utf-8")
-                        .body(html);
-                }
-            }
+impl<T: Serialize> Responder for Inertia<T> {
+    type Body = BoxBody;
 
-            HttpResponse::Ok()
-                .content_type("application/json")
-                .body(ctx.data_page)
-        }
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        self.render_response(req)
     }
 }
 
@@ -244,8 +158,7 @@ pub async fn example_handler(
         serde_json::json!({"prop_key": "prop_value"}),
         req.uri().to_string().as_str(),
     );
-    #[cfg(debug_assertions)]
-    eprintln!("Handler - Request: {:?}", req);
+    trace_event!(trace, method = %req.method(), path = req.path(), "example handler");
 
     inertia.into_response(&req).await
 }
@@ -260,6 +173,9 @@ impl VersionMiddleware {
     }
 }
 
+/// Whether a request's `X-Inertia-Version` is current.
+pub(crate) type AcceptsVersionFn = Arc<dyn Fn(Option<&str>) -> bool + Send + Sync>;
+
 impl<S, B> Transform<S, ServiceRequest> for VersionMiddleware
 where
     S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
@@ -273,16 +189,17 @@ where
     type Future = Ready<Result<Self::Transform, Self::InitError>>;
 
     fn new_transform(&self, service: S) -> Self::Future {
+        let version = self.version.clone();
         ok(VersionMiddlewareService {
             service,
-            version: self.version.clone(),
+            accepts: Arc::new(move |request_version| request_version == Some(version.as_str())),
         })
     }
 }
 
 pub struct VersionMiddlewareService<S> {
-    service: S,
-    version: String,
+    pub(crate) service: S,
+    pub(crate) accepts: AcceptsVersionFn,
 }
 
 impl<S, B> Service<ServiceRequest> for VersionMiddlewareService<S>
@@ -298,8 +215,17 @@ where
     forward_ready!(service);
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
-        #[cfg(debug_assertions)]
-        eprintln!("Middleware - ServiceRequest: {:?}", req);
+        trace_event!(
+            trace,
+            method = %req.method(),
+            path = req.path(),
+            headers = ?crate::trace::redacted_headers(
+                req.headers()
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_bytes()))
+            ),
+            "checking asset version"
+        );
 
         if req.method() == http::Method::GET && req.headers().contains_key(X_INERTIA) {
             let request_version = req
@@ -307,22 +233,23 @@ where
                 .get(X_INERTIA_VERSION)
                 .map(|v| v.to_str().unwrap_or("").to_string());
 
-            #[cfg(debug_assertions)]
-            eprintln!("Middleware - Request Version: {:?}", request_version);
-
-            if request_version.is_none() || request_version.as_deref() != Some(&self.version) {
-                let uri = format!(
-                    "{}?location={}",
-                    "/inertia-rs/version-conflict",
-                    req.uri().path()
-                );
-
+            // Scopes with their own root view have their own version, which the page render
+            // checks against the view it picks.
+            let factory = ResponseFactory::find(req.request());
+            let accepted = (self.accepts)(request_version.as_deref())
+                || factory.is_some_and(|factory| {
+                    factory.is_root_view_version(request_version.as_deref())
+                });
+            if !accepted {
+                if let Some(factory) = factory {
+                    factory.record_version_conflict(
+                        &req.uri().to_string(),
+                        request_version.as_deref(),
+                    );
+                }
                 Box::pin(async move {
                     let (req, _) = req.into_parts();
-                    let res = HttpResponse::Conflict()
-                        .append_header((X_INERTIA_LOCATION, uri))
-                        .finish()
-                        .map_into_right_body();
+                    let res = conflict(&req.uri().to_string()).map_into_right_body();
                     Ok(ServiceResponse::new(req, res))
                 })
             } else {
@@ -341,3 +268,49 @@ where
         }
     }
 }
+
+/// Request marker set by [`EncryptHistoryMiddleware`].
+#[derive(Clone, Copy)]
+pub struct EncryptHistory;
+
+/// Turns on history encryption for every Inertia page rendered inside the wrapped scope.
+pub struct EncryptHistoryMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for EncryptHistoryMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = EncryptHistoryMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(EncryptHistoryMiddlewareService { service })
+    }
+}
+
+pub struct EncryptHistoryMiddlewareService<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for EncryptHistoryMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = S::Future;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        req.extensions_mut().insert(EncryptHistory);
+        self.service.call(req)
+    }
+}
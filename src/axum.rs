@@ -0,0 +1,292 @@
+//! The axum adapter: an [`IntoResponse`] impl for [`Inertia`], an extractor for the Inertia
+//! request headers and a tower [`Layer`] over the [`protocol`](crate::protocol) core.
+//!
+//! Handlers can't see the request when they turn a page into a response, so returning an
+//! [`Inertia`] only stores the page. [`InertiaLayer`] renders it with the request's headers
+//! and the [`ResponseFactory`], and must wrap every route that returns a page.
+//!
+//! ```ignore
+//! use actix_inertia::axum::InertiaLayer;
+//!
+//! async fn dashboard(request: InertiaRequest) -> Inertia<Value> {
+//!     Inertia::new("Dashboard".to_string(), json!({ "user": "Ada" }), request.url)
+//! }
+//!
+//! let app = Router::new()
+//!     .route("/", get(dashboard))
+//!     .layer(InertiaLayer::new(factory));
+//! ```
+
+use crate::error::InertiaError;
+use crate::factory::ResponseFactory;
+use crate::protocol::{InertiaRequest, Rendered, ERRORS_COOKIE};
+use crate::{Inertia, X_INERTIA_LOCATION};
+use ::axum::extract::{FromRequestParts, Request};
+use ::axum::response::{IntoResponse, Response};
+use http::{header, request::Parts, HeaderName, HeaderValue, Method, StatusCode};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+type ShareFn = Arc<dyn Fn(&Parts) -> Value + Send + Sync>;
+
+/// A page returned by a handler, waiting for [`InertiaLayer`] to render it.
+#[derive(Clone)]
+struct PendingPage(Arc<Mutex<Option<Inertia<Value>>>>);
+
+impl<T: Serialize> IntoResponse for Inertia<T> {
+    /// Without an [`InertiaLayer`] around the route this stays a `500 Internal Server Error`.
+    fn into_response(self) -> Response {
+        let mut response = InertiaError::MissingFactory.into_response();
+        response
+            .extensions_mut()
+            .insert(PendingPage(Arc::new(Mutex::new(Some(self.into_value())))));
+        response
+    }
+}
+
+/// Reads the Inertia request headers, e.g. to check for partial reloads in a handler.
+impl<S: Send + Sync> FromRequestParts<S> for InertiaRequest {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<InertiaRequest>()
+            .cloned()
+            .unwrap_or_else(|| InertiaRequest::from_parts(parts)))
+    }
+}
+
+/// Renders the [`Inertia`] pages returned by the wrapped routes.
+///
+/// From the outside in: stale asset versions get a `409`, shared props are computed, the
+/// factory is made available to handlers as `Extension<Arc<ResponseFactory>>`, and `302`
+/// redirects after `PUT`, `PATCH` and `DELETE` visits become `303` so the browser follows
+/// them with a `GET`.
+#[derive(Clone)]
+pub struct InertiaLayer {
+    factory: Arc<ResponseFactory>,
+    version_check: bool,
+    see_other_redirects: bool,
+    shared: Vec<ShareFn>,
+}
+
+impl InertiaLayer {
+    pub fn new(factory: ResponseFactory) -> Self {
+        Self::from_arc(Arc::new(factory))
+    }
+
+    /// Uses a factory that is already shared, e.g. with a second router.
+    pub fn from_arc(factory: Arc<ResponseFactory>) -> Self {
+        Self {
+            factory,
+            version_check: true,
+            see_other_redirects: true,
+            shared: Vec::new(),
+        }
+    }
+
+    /// Whether to answer Inertia visits with a stale `X-Inertia-Version` with `409 Conflict`.
+    /// Turning this off also turns off the check pages make.
+    pub fn version_check(mut self, check: bool) -> Self {
+        self.version_check = check;
+        self
+    }
+
+    /// Whether to turn `302` redirects after `PUT`, `PATCH` and `DELETE` visits into `303`.
+    pub fn see_other_redirects(mut self, see_other: bool) -> Self {
+        self.see_other_redirects = see_other;
+        self
+    }
+
+    /// Adds props computed per request, e.g. the current user, to every page.
+    ///
+    /// `share` must return a JSON object. It can be called several times.
+    pub fn share<F>(mut self, share: F) -> Self
+    where
+        F: Fn(&Parts) -> Value + Send + Sync + 'static,
+    {
+        self.shared.push(Arc::new(share));
+        self
+    }
+}
+
+impl<S> Layer<S> for InertiaLayer {
+    type Service = InertiaService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        InertiaService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct InertiaService<S> {
+    inner: S,
+    layer: InertiaLayer,
+}
+
+impl<S> Service<Request> for InertiaService<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let factory = self.layer.factory.clone();
+        let (mut parts, body) = req.into_parts();
+        let mut request = InertiaRequest::from_parts(&parts);
+        trace_event!(
+            trace,
+            method = %parts.method,
+            path = parts.uri.path(),
+            headers = ?crate::trace::redacted_headers(
+                parts
+                    .headers
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_bytes()))
+            ),
+            "handling Inertia request"
+        );
+
+        if self.layer.version_check
+            && factory.has_version()
+            && request.version_conflict(|version| factory.accepts_version(version))
+        {
+            factory.record_version_conflict(&request.url, request.version.as_deref());
+            let response = conflict(&request.url);
+            return Box::pin(async move { Ok(response) });
+        }
+
+        if !self.layer.shared.is_empty() {
+            trace_span!("inertia.shared_props", count = self.layer.shared.len());
+            let mut props = Map::new();
+            for share in &self.layer.shared {
+                if let Value::Object(values) = share(&parts) {
+                    props.extend(values);
+                }
+            }
+            request.shared_props = props;
+        }
+        request.skip_version_check = !self.layer.version_check;
+
+        let see_other = self.layer.see_other_redirects
+            && request.inertia
+            && matches!(request.method, Method::PUT | Method::PATCH | Method::DELETE);
+
+        parts.extensions.insert(request.clone());
+        parts.extensions.insert(factory.clone());
+        let fut = self.inner.call(Request::from_parts(parts, body));
+
+        Box::pin(async move {
+            let mut res = fut.await?;
+            let pending = res
+                .extensions_mut()
+                .remove::<PendingPage>()
+                .and_then(|PendingPage(page)| page.lock().ok()?.take());
+            if let Some(page) = pending {
+                res = keep_handler_parts(render(page, &request, &factory), res);
+            }
+            if see_other && res.status() == StatusCode::FOUND {
+                *res.status_mut() = StatusCode::SEE_OTHER;
+            }
+            Ok(res)
+        })
+    }
+}
+
+/// The header constants are mixed case, which `HeaderName::from_static` rejects.
+fn header_name(name: &str) -> HeaderName {
+    HeaderName::from_bytes(name.as_bytes()).expect("valid header name")
+}
+
+fn conflict(location: &str) -> Response {
+    let mut response = StatusCode::CONFLICT.into_response();
+    if let Ok(location) = HeaderValue::from_str(location) {
+        response
+            .headers_mut()
+            .insert(header_name(X_INERTIA_LOCATION), location);
+    }
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_static("X-Inertia"));
+    response
+}
+
+/// Copies what the handler returned next to the page, e.g. the status and cookie of
+/// `(StatusCode::CREATED, [(SET_COOKIE, cookie)], Inertia::new(..))`, onto the rendered page.
+/// The placeholder's own `500` and content headers are left behind, and a conflict, `304` or
+/// render error keeps its status.
+fn keep_handler_parts(mut rendered: Response, pending: Response) -> Response {
+    let (parts, _) = pending.into_parts();
+    if rendered.status() == StatusCode::OK && parts.status != StatusCode::INTERNAL_SERVER_ERROR {
+        *rendered.status_mut() = parts.status;
+    }
+    for (name, value) in &parts.headers {
+        if name != header::CONTENT_TYPE && name != header::CONTENT_LENGTH {
+            rendered.headers_mut().append(name, value.clone());
+        }
+    }
+    rendered
+}
+
+/// Renders `page`, answering `500 Internal Server Error` if that fails.
+fn render(page: Inertia<Value>, request: &InertiaRequest, factory: &ResponseFactory) -> Response {
+    let component = page.component.clone();
+    let (rendered, diagnostics) = match page.render_page_with_diagnostics(request, Some(factory)) {
+        Ok(rendered) => rendered,
+        Err(err) => {
+            report!(
+                error,
+                "failed to render Inertia component `{component}`: {err}"
+            );
+            return err.into_response();
+        }
+    };
+
+    let mut headers = rendered.headers(request, Some(factory));
+    if let Some(diagnostics) = diagnostics {
+        headers.extend(diagnostics.headers(request));
+    }
+    let forget_errors = rendered.forgets_errors(request);
+    let mut response = match rendered {
+        Rendered::Json(page) | Rendered::NoRootView(page) => {
+            ([(header::CONTENT_TYPE, "application/json")], page).into_response()
+        }
+        Rendered::Html(html) => {
+            ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], html).into_response()
+        }
+        Rendered::Conflict(_) => StatusCode::CONFLICT.into_response(),
+        Rendered::NotModified(_) => StatusCode::NOT_MODIFIED.into_response(),
+    };
+
+    for (name, value) in headers {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            response.headers_mut().append(header_name(name), value);
+        }
+    }
+    if forget_errors {
+        let cookie = format!("{ERRORS_COOKIE}=; Path=/; Max-Age=0");
+        if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+            response.headers_mut().append(header::SET_COOKIE, cookie);
+        }
+    }
+
+    response
+}
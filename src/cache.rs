@@ -0,0 +1,76 @@
+//! `Cache-Control` policies for Inertia responses.
+
+use std::fmt;
+use std::time::Duration;
+
+/// A `Cache-Control` header value.
+///
+/// ```ignore
+/// // private, max-age=30, stale-while-revalidate=60
+/// CacheControl::private()
+///     .max_age(Duration::from_secs(30))
+///     .stale_while_revalidate(Duration::from_secs(60))
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheControl {
+    public: bool,
+    no_store: bool,
+    max_age: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
+}
+
+impl CacheControl {
+    /// Only the browser may store the response.
+    pub fn private() -> Self {
+        Self {
+            public: false,
+            no_store: false,
+            max_age: None,
+            stale_while_revalidate: None,
+        }
+    }
+
+    /// Shared caches such as CDNs may store the response too.
+    pub fn public() -> Self {
+        Self {
+            public: true,
+            ..Self::private()
+        }
+    }
+
+    /// Nothing may store the response.
+    pub fn no_store() -> Self {
+        Self {
+            no_store: true,
+            ..Self::private()
+        }
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Lets caches answer with the stale response for this long while they fetch a new one.
+    pub fn stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.stale_while_revalidate = Some(window);
+        self
+    }
+}
+
+impl fmt::Display for CacheControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.no_store {
+            return f.write_str("no-store");
+        }
+
+        f.write_str(if self.public { "public" } else { "private" })?;
+        if let Some(max_age) = self.max_age {
+            write!(f, ", max-age={}", max_age.as_secs())?;
+        }
+        if let Some(window) = self.stale_while_revalidate {
+            write!(f, ", stale-while-revalidate={}", window.as_secs())?;
+        }
+        Ok(())
+    }
+}
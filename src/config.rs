@@ -0,0 +1,405 @@
+//! One place to configure a [`ResponseFactory`], loaded from code, a TOML file or the
+//! environment and checked once at [`InertiaConfig::build`].
+//!
+//! ```toml
+//! root_view = "templates/app.html"
+//! version_manifest = "public/build/manifest.json"
+//! encrypt_history = true
+//! errors_key = "errors"
+//!
+//! [root_views.admin]
+//! path = "templates/admin.html"
+//! version = "admin-1"
+//! vite_entry = "/resources/js/admin.ts"
+//! ```
+
+use crate::cache::CacheControl;
+use crate::error::InertiaError;
+use crate::factory::ResponseFactory;
+use crate::root_view::RootView;
+use crate::size::SizeLimit;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Where the asset version sent with every page comes from.
+#[derive(Clone)]
+enum VersionSource {
+    Fixed(String),
+    /// A hash of the build manifest, computed once at [`InertiaConfig::build`].
+    Manifest(PathBuf),
+    Dynamic(Arc<dyn Fn() -> String + Send + Sync>),
+}
+
+/// Settings for a server-side rendering server.
+///
+/// The crate doesn't render on the server yet, so [`InertiaConfig::build`] rejects
+/// `enabled = true` instead of silently rendering on the client.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SsrConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_ssr_url")]
+    pub url: String,
+}
+
+fn default_ssr_url() -> String {
+    "http://127.0.0.1:13714".to_string()
+}
+
+impl Default for SsrConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: default_ssr_url(),
+        }
+    }
+}
+
+/// The shape of an Inertia TOML file.
+#[cfg(feature = "toml")]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileConfig {
+    root_view: Option<PathBuf>,
+    version: Option<String>,
+    version_manifest: Option<PathBuf>,
+    encrypt_history: Option<bool>,
+    clear_history: Option<bool>,
+    errors_key: Option<String>,
+    dev_mode: Option<bool>,
+    ssr: Option<SsrConfig>,
+    #[serde(default)]
+    root_views: BTreeMap<String, FileRootView>,
+}
+
+#[cfg(feature = "toml")]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileRootView {
+    path: String,
+    version: Option<String>,
+    vite_entry: Option<String>,
+}
+
+/// Builds a [`ResponseFactory`].
+///
+/// Nothing is checked until [`build`](InertiaConfig::build), which fails when the root view
+/// is not set, cannot be read or has no `{{DATA_PAGE}}` placeholder.
+#[derive(Clone)]
+pub struct InertiaConfig {
+    root_view: Option<PathBuf>,
+    root_views: BTreeMap<String, RootView>,
+    version: Option<VersionSource>,
+    encrypt_history: bool,
+    clear_history: bool,
+    errors_key: String,
+    dev_mode: bool,
+    ssr: SsrConfig,
+    prefetch_cache: Option<Option<CacheControl>>,
+    html_cache: Option<CacheControl>,
+    json_cache: Option<CacheControl>,
+    size_limit: Option<SizeLimit>,
+}
+
+impl Default for InertiaConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InertiaConfig {
+    pub fn new() -> Self {
+        Self {
+            root_view: None,
+            root_views: BTreeMap::new(),
+            version: None,
+            encrypt_history: false,
+            clear_history: false,
+            errors_key: "errors".to_string(),
+            dev_mode: cfg!(debug_assertions),
+            ssr: SsrConfig::default(),
+            prefetch_cache: None,
+            html_cache: None,
+            json_cache: None,
+            size_limit: None,
+        }
+    }
+
+    /// Reads a TOML file. Keys that are not set keep their defaults.
+    #[cfg(feature = "toml")]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, InertiaError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|err| InertiaError::Config(format!("{}: {err}", path.display())))?;
+        Self::new().toml(&contents)
+    }
+
+    /// Applies the settings of a TOML document on top of this configuration.
+    #[cfg(feature = "toml")]
+    pub fn toml(mut self, contents: &str) -> Result<Self, InertiaError> {
+        let file: FileConfig =
+            toml::from_str(contents).map_err(|err| InertiaError::Config(err.to_string()))?;
+
+        if let Some(root_view) = file.root_view {
+            self.root_view = Some(root_view);
+        }
+        match (file.version, file.version_manifest) {
+            (Some(_), Some(_)) => {
+                return Err(InertiaError::Config(
+                    "set either `version` or `version_manifest`, not both".to_string(),
+                ))
+            }
+            (Some(version), None) => self.version = Some(VersionSource::Fixed(version)),
+            (None, Some(manifest)) => self.version = Some(VersionSource::Manifest(manifest)),
+            (None, None) => {}
+        }
+        if let Some(encrypt) = file.encrypt_history {
+            self.encrypt_history = encrypt;
+        }
+        if let Some(clear) = file.clear_history {
+            self.clear_history = clear;
+        }
+        if let Some(key) = file.errors_key {
+            self.errors_key = key;
+        }
+        if let Some(dev_mode) = file.dev_mode {
+            self.dev_mode = dev_mode;
+        }
+        if let Some(ssr) = file.ssr {
+            self.ssr = ssr;
+        }
+        for (name, view) in file.root_views {
+            let mut root_view = RootView::new(view.path);
+            if let Some(version) = view.version {
+                root_view = root_view.version(version);
+            }
+            if let Some(entry) = view.vite_entry {
+                root_view = root_view.vite_entry(entry);
+            }
+            self.root_views.insert(name, root_view);
+        }
+        Ok(self)
+    }
+
+    /// Reads the `INERTIA_*` environment variables.
+    pub fn from_env() -> Result<Self, InertiaError> {
+        Self::new().env()
+    }
+
+    /// Applies the `INERTIA_*` environment variables on top of this configuration, e.g. to
+    /// override a TOML file per deployment.
+    pub fn env(self) -> Result<Self, InertiaError> {
+        self.vars(std::env::vars())
+    }
+
+    /// Applies `INERTIA_*` variables from `vars` instead of the process environment.
+    ///
+    /// Recognised variables are `INERTIA_ROOT_VIEW`, `INERTIA_VERSION`,
+    /// `INERTIA_VERSION_MANIFEST`, `INERTIA_ENCRYPT_HISTORY`, `INERTIA_CLEAR_HISTORY`,
+    /// `INERTIA_ERRORS_KEY`, `INERTIA_DEV_MODE`, `INERTIA_SSR_ENABLED` and `INERTIA_SSR_URL`.
+    /// Other `INERTIA_*` variables are ignored with a warning, as they may belong to other
+    /// tools. Setting both `INERTIA_VERSION` and `INERTIA_VERSION_MANIFEST` is an error.
+    pub fn vars<I, K, V>(mut self, vars: I) -> Result<Self, InertiaError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let (mut fixed, mut manifest) = (false, false);
+        for (name, value) in vars {
+            let name = name.as_ref();
+            let value = value.into();
+            match name {
+                "INERTIA_ROOT_VIEW" => self.root_view = Some(value.into()),
+                "INERTIA_VERSION" => {
+                    fixed = true;
+                    self.version = Some(VersionSource::Fixed(value));
+                }
+                "INERTIA_VERSION_MANIFEST" => {
+                    manifest = true;
+                    self.version = Some(VersionSource::Manifest(value.into()));
+                }
+                "INERTIA_ENCRYPT_HISTORY" => self.encrypt_history = parse_bool(name, &value)?,
+                "INERTIA_CLEAR_HISTORY" => self.clear_history = parse_bool(name, &value)?,
+                "INERTIA_ERRORS_KEY" => self.errors_key = value,
+                "INERTIA_DEV_MODE" => self.dev_mode = parse_bool(name, &value)?,
+                "INERTIA_SSR_ENABLED" => self.ssr.enabled = parse_bool(name, &value)?,
+                "INERTIA_SSR_URL" => self.ssr.url = value,
+                _ if name.starts_with("INERTIA_") => {
+                    report!(warn, "ignoring unknown configuration variable {name}");
+                }
+                _ => {}
+            }
+        }
+        if fixed && manifest {
+            return Err(InertiaError::Config(
+                "set either `INERTIA_VERSION` or `INERTIA_VERSION_MANIFEST`, not both".to_string(),
+            ));
+        }
+        Ok(self)
+    }
+
+    /// The HTML template pages are rendered into on full page loads.
+    pub fn root_view(mut self, path: impl Into<PathBuf>) -> Self {
+        self.root_view = Some(path.into());
+        self
+    }
+
+    /// Adds a named root view, see [`RootView`].
+    pub fn add_root_view(mut self, name: impl Into<String>, view: RootView) -> Self {
+        self.root_views.insert(name.into(), view);
+        self
+    }
+
+    /// Sends the same asset version with every page.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(VersionSource::Fixed(version.into()));
+        self
+    }
+
+    /// Derives the asset version from the contents of a build manifest, e.g. Vite's
+    /// `manifest.json`, so every deploy with new assets gets a new version.
+    pub fn version_manifest(mut self, path: impl Into<PathBuf>) -> Self {
+        self.version = Some(VersionSource::Manifest(path.into()));
+        self
+    }
+
+    /// Computes the asset version on every request.
+    pub fn version_with<F>(mut self, version: F) -> Self
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        self.version = Some(VersionSource::Dynamic(Arc::new(version)));
+        self
+    }
+
+    pub fn encrypt_history(mut self, encrypt: bool) -> Self {
+        self.encrypt_history = encrypt;
+        self
+    }
+
+    pub fn clear_history(mut self, clear: bool) -> Self {
+        self.clear_history = clear;
+        self
+    }
+
+    /// The prop that flashed validation errors are merged into.
+    pub fn errors_key(mut self, key: impl Into<String>) -> Self {
+        self.errors_key = key.into();
+        self
+    }
+
+    /// Turns on development checks. Defaults to debug builds.
+    pub fn dev_mode(mut self, dev_mode: bool) -> Self {
+        self.dev_mode = dev_mode;
+        self
+    }
+
+    pub fn ssr(mut self, ssr: SsrConfig) -> Self {
+        self.ssr = ssr;
+        self
+    }
+
+    /// The `Cache-Control` for prefetch responses, see [`ResponseFactory::set_prefetch_cache`].
+    pub fn prefetch_cache(mut self, cache: Option<CacheControl>) -> Self {
+        self.prefetch_cache = Some(cache);
+        self
+    }
+
+    /// The `Cache-Control` for full page loads, see [`ResponseFactory::set_html_cache`].
+    pub fn html_cache(mut self, cache: CacheControl) -> Self {
+        self.html_cache = Some(cache);
+        self
+    }
+
+    /// The `Cache-Control` for JSON page objects, see [`ResponseFactory::set_json_cache`].
+    pub fn json_cache(mut self, cache: CacheControl) -> Self {
+        self.json_cache = Some(cache);
+        self
+    }
+
+    /// Limits the serialized size of page objects, see [`SizeLimit`].
+    pub fn size_limit(mut self, limit: SizeLimit) -> Self {
+        self.size_limit = Some(limit);
+        self
+    }
+
+    /// Checks the configuration and creates the factory.
+    pub fn build(self) -> Result<ResponseFactory, InertiaError> {
+        let root_view = self
+            .root_view
+            .ok_or_else(|| InertiaError::Config("no root view is set".to_string()))?;
+        check_template(&root_view)?;
+        for view in self.root_views.values() {
+            check_template(Path::new(view.path()))?;
+        }
+        if self.errors_key.is_empty() {
+            return Err(InertiaError::Config("errors_key is empty".to_string()));
+        }
+        if self.ssr.enabled {
+            return Err(InertiaError::Config(
+                "server-side rendering is not supported yet, disable `ssr.enabled`".to_string(),
+            ));
+        }
+
+        let mut factory = ResponseFactory::new();
+        factory.set_root_view(&root_view.to_string_lossy());
+        match self.version {
+            Some(VersionSource::Fixed(version)) => factory.set_version(move || version.clone()),
+            Some(VersionSource::Manifest(path)) => {
+                let version = manifest_version(&path)?;
+                factory.set_version(move || version.clone());
+            }
+            Some(VersionSource::Dynamic(version)) => factory.set_version(move || version()),
+            None => {}
+        }
+        for (name, view) in self.root_views {
+            factory.add_root_view(&name, view);
+        }
+        factory.set_encrypt_history(self.encrypt_history);
+        factory.set_clear_history(self.clear_history);
+        factory.set_errors_key(&self.errors_key);
+        factory.set_dev_mode(self.dev_mode);
+        if let Some(cache) = self.prefetch_cache {
+            factory.set_prefetch_cache(cache);
+        }
+        factory.set_html_cache(self.html_cache);
+        factory.set_json_cache(self.json_cache);
+        if let Some(limit) = self.size_limit {
+            factory.set_size_limit(limit);
+        }
+        Ok(factory)
+    }
+}
+
+fn check_template(path: &Path) -> Result<(), InertiaError> {
+    let template = fs::read_to_string(path)
+        .map_err(|err| InertiaError::Config(format!("root view {}: {err}", path.display())))?;
+    if !template.contains("{{DATA_PAGE}}") {
+        return Err(InertiaError::Config(format!(
+            "root view {} has no {{{{DATA_PAGE}}}} placeholder",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+fn parse_bool(name: &str, value: &str) -> Result<bool, InertiaError> {
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(InertiaError::Config(format!(
+            "{name} must be true or false, got `{value}`"
+        ))),
+    }
+}
+
+fn manifest_version(path: &PathBuf) -> Result<String, InertiaError> {
+    let manifest = fs::read(path)
+        .map_err(|err| InertiaError::Version(format!("{}: {err}", path.display())))?;
+    Ok(crate::hash::stable_hash(&manifest))
+}
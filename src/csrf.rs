@@ -0,0 +1,213 @@
+use crate::validation::ValidationErrors;
+use actix_service::{forward_ready, Service, Transform};
+use actix_web::body::EitherBody;
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::{
+    dev::ServiceRequest, dev::ServiceResponse, http, Error, HttpRequest, HttpResponse, Result,
+};
+use futures::future::{ok, Ready};
+use futures_util::future::LocalBoxFuture;
+use rand::distributions::{Alphanumeric, DistString};
+
+pub static XSRF_TOKEN_COOKIE: &str = "XSRF-TOKEN";
+pub static X_XSRF_TOKEN: &str = "X-XSRF-TOKEN";
+pub static X_CSRF_TOKEN: &str = "X-CSRF-TOKEN";
+
+/// Status Laravel and the Inertia client use for an expired CSRF token.
+pub const PAGE_EXPIRED: u16 = 419;
+
+const TOKEN_LENGTH: usize = 40;
+
+/// How [`CsrfMiddleware`] answers a request whose token does not match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsrfFailure {
+    /// A `419 Page Expired` response.
+    PageExpired,
+    /// A `303` back to the previous page with a `csrf` validation error.
+    RedirectBack,
+}
+
+/// Double-submit CSRF protection compatible with Inertia's axios defaults.
+///
+/// Sets an `XSRF-TOKEN` cookie that axios copies into the `X-XSRF-TOKEN` header, and
+/// rejects `POST`, `PUT`, `PATCH` and `DELETE` requests whose header does not match it.
+#[derive(Clone)]
+pub struct CsrfMiddleware {
+    cookie_name: String,
+    secure: bool,
+    exclude: Vec<String>,
+    failure: CsrfFailure,
+}
+
+impl Default for CsrfMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsrfMiddleware {
+    pub fn new() -> Self {
+        Self {
+            cookie_name: XSRF_TOKEN_COOKIE.to_string(),
+            secure: false,
+            exclude: Vec::new(),
+            failure: CsrfFailure::PageExpired,
+        }
+    }
+
+    pub fn cookie_name(mut self, name: &str) -> Self {
+        self.cookie_name = name.to_string();
+        self
+    }
+
+    /// Only send the token cookie over HTTPS.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Skip the check for paths starting with `prefix`, e.g. webhooks.
+    pub fn exclude(mut self, prefix: &str) -> Self {
+        self.exclude.push(prefix.to_string());
+        self
+    }
+
+    pub fn on_failure(mut self, failure: CsrfFailure) -> Self {
+        self.failure = failure;
+        self
+    }
+
+    fn requires_token(&self, req: &ServiceRequest) -> bool {
+        let state_changing = matches!(
+            *req.method(),
+            http::Method::POST | http::Method::PUT | http::Method::PATCH | http::Method::DELETE
+        );
+        state_changing
+            && !self
+                .exclude
+                .iter()
+                .any(|prefix| req.path().starts_with(prefix.as_str()))
+    }
+
+    fn failure_response(&self, req: &HttpRequest) -> HttpResponse {
+        match self.failure {
+            CsrfFailure::RedirectBack => {
+                let mut errors = ValidationErrors::new();
+                errors.add("csrf", "The page expired, please try again.");
+                errors.redirect_back(req)
+            }
+            // The Inertia client shows non-Inertia responses in a modal, so give it a page.
+            CsrfFailure::PageExpired => HttpResponse::build(page_expired())
+                .content_type("text/html; charset=utf-8")
+                .body("<h1>Page Expired</h1><p>Please refresh the page and try again.</p>"),
+        }
+    }
+
+    fn cookie(&self, token: String) -> Cookie<'static> {
+        Cookie::build(self.cookie_name.clone(), token)
+            .path("/")
+            .same_site(SameSite::Lax)
+            .secure(self.secure)
+            .finish()
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for CsrfMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = CsrfMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(CsrfMiddlewareService {
+            service,
+            config: self.clone(),
+        })
+    }
+}
+
+pub struct CsrfMiddlewareService<S> {
+    service: S,
+    config: CsrfMiddleware,
+}
+
+impl<S, B> Service<ServiceRequest> for CsrfMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let cookie_token = req
+            .cookie(&self.config.cookie_name)
+            .map(|cookie| cookie.value().to_string())
+            .filter(|token| !token.is_empty());
+        let new_cookie = match cookie_token {
+            Some(_) => None,
+            None => Some(self.config.cookie(generate_token())),
+        };
+
+        if self.config.requires_token(&req) {
+            let header_token = req
+                .headers()
+                .get(X_XSRF_TOKEN)
+                .or_else(|| req.headers().get(X_CSRF_TOKEN))
+                .and_then(|v| v.to_str().ok());
+
+            let valid = match (cookie_token.as_deref(), header_token) {
+                (Some(expected), Some(given)) => tokens_match(expected, given),
+                _ => false,
+            };
+
+            if !valid {
+                let (req, _) = req.into_parts();
+                let mut res = self.config.failure_response(&req);
+                if let Some(cookie) = &new_cookie {
+                    let _ = res.add_cookie(cookie);
+                }
+                return Box::pin(async move {
+                    Ok(ServiceResponse::new(req, res.map_into_right_body()))
+                });
+            }
+        }
+
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            let mut res = fut.await?;
+            if let Some(cookie) = &new_cookie {
+                res.response_mut().add_cookie(cookie)?;
+            }
+            Ok(res.map_into_left_body())
+        })
+    }
+}
+
+fn page_expired() -> http::StatusCode {
+    http::StatusCode::from_u16(PAGE_EXPIRED).expect("419 is a valid status code")
+}
+
+fn generate_token() -> String {
+    Alphanumeric.sample_string(&mut rand::thread_rng(), TOKEN_LENGTH)
+}
+
+/// Compares tokens without short-circuiting on the first differing byte.
+fn tokens_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
@@ -0,0 +1,105 @@
+#[cfg(feature = "actix")]
+use actix_web::{http, HttpResponse, ResponseError};
+#[cfg(feature = "axum")]
+use axum::response::{IntoResponse, Response};
+use std::fmt;
+
+/// Errors raised while rendering an Inertia page.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InertiaError {
+    /// The props or the page object could not be serialized to JSON.
+    Serialization(serde_json::Error),
+    /// The root view could not be read or has no `{{DATA_PAGE}}` placeholder.
+    Template(String),
+    /// Server-side rendering of the page failed.
+    Ssr(String),
+    /// The current asset version could not be determined.
+    Version(String),
+    /// The page component is missing from the [`PagesDirectory`](crate::PagesDirectory).
+    MissingComponent(String),
+    /// No [`ResponseFactory`](crate::ResponseFactory) is registered for the request.
+    MissingFactory,
+    /// The [`InertiaConfig`](crate::InertiaConfig) is invalid or could not be loaded.
+    Config(String),
+    /// The page object is over the [`SizeLimit`](crate::SizeLimit) and that is configured to
+    /// reject it.
+    TooLarge(String),
+}
+
+impl fmt::Display for InertiaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InertiaError::Serialization(err) => {
+                write!(f, "failed to serialize Inertia page: {err}")
+            }
+            InertiaError::Template(msg) => write!(f, "failed to render Inertia root view: {msg}"),
+            InertiaError::Ssr(msg) => write!(f, "Inertia server-side rendering failed: {msg}"),
+            InertiaError::Version(msg) => {
+                write!(f, "failed to determine Inertia asset version: {msg}")
+            }
+            InertiaError::MissingComponent(msg) => f.write_str(msg),
+            InertiaError::MissingFactory => write!(
+                f,
+                "no ResponseFactory is registered, wrap the app in `InertiaLayer` or add it with \
+                 `app_data(web::Data::new(factory))`"
+            ),
+            InertiaError::Config(msg) => write!(f, "invalid Inertia configuration: {msg}"),
+            InertiaError::TooLarge(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for InertiaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InertiaError::Serialization(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for InertiaError {
+    fn from(err: serde_json::Error) -> Self {
+        InertiaError::Serialization(err)
+    }
+}
+
+#[cfg(feature = "actix")]
+impl ResponseError for InertiaError {
+    fn status_code(&self) -> http::StatusCode {
+        http::StatusCode::INTERNAL_SERVER_ERROR
+    }
+
+    /// Debug builds show the error, release builds a generic message.
+    fn error_response(&self) -> HttpResponse {
+        let body = if cfg!(debug_assertions) {
+            self.to_string()
+        } else {
+            "Internal Server Error".to_string()
+        };
+
+        HttpResponse::build(self.status_code())
+            .content_type("text/plain; charset=utf-8")
+            .body(body)
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for InertiaError {
+    /// Debug builds show the error, release builds a generic message.
+    fn into_response(self) -> Response {
+        let body = if cfg!(debug_assertions) {
+            self.to_string()
+        } else {
+            "Internal Server Error".to_string()
+        };
+
+        (
+            ::http::StatusCode::INTERNAL_SERVER_ERROR,
+            [(::http::header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            body,
+        )
+            .into_response()
+    }
+}
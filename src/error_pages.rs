@@ -0,0 +1,140 @@
+use crate::{Inertia, X_INERTIA};
+use actix_service::{forward_ready, Service, Transform};
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::{dev::ServiceRequest, dev::ServiceResponse, http, Error, Result};
+use futures::future::{ok, Ready};
+use futures_util::future::LocalBoxFuture;
+
+/// Renders error responses as an Inertia page component, e.g. `Error` with `{status, message}`,
+/// so Inertia visits show a page instead of the raw error in a modal.
+///
+/// Debug builds pass the error's own message to the page, release builds a generic one.
+#[derive(Clone)]
+pub struct ErrorPages {
+    component: String,
+    statuses: Vec<http::StatusCode>,
+    show_details: bool,
+}
+
+impl ErrorPages {
+    /// Renders `403`, `404`, `419`, `500` and `503` responses with `component`.
+    pub fn new(component: &str) -> Self {
+        Self {
+            component: component.to_string(),
+            statuses: [403, 404, 419, 500, 503]
+                .iter()
+                .filter_map(|status| http::StatusCode::from_u16(*status).ok())
+                .collect(),
+            show_details: cfg!(debug_assertions),
+        }
+    }
+
+    /// Replaces the rendered status codes.
+    pub fn only(mut self, statuses: &[u16]) -> Self {
+        self.statuses = statuses
+            .iter()
+            .filter_map(|status| http::StatusCode::from_u16(*status).ok())
+            .collect();
+        self
+    }
+
+    /// Overrides whether error details reach the page, which defaults to debug builds only.
+    pub fn show_details(mut self, show: bool) -> Self {
+        self.show_details = show;
+        self
+    }
+
+    fn message(&self, status: http::StatusCode, error: Option<&Error>) -> String {
+        if self.show_details {
+            if let Some(error) = error {
+                return error.to_string();
+            }
+        }
+
+        match status.as_u16() {
+            403 => "Sorry, you are forbidden from accessing this page.".to_string(),
+            404 => "Sorry, the page you are looking for could not be found.".to_string(),
+            419 => "The page expired, please try again.".to_string(),
+            500 => "Whoops, something went wrong on our servers.".to_string(),
+            503 => "Sorry, we are doing some maintenance. Please check back soon.".to_string(),
+            _ => status.canonical_reason().unwrap_or("Error").to_string(),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ErrorPages
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = ErrorPagesService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(ErrorPagesService {
+            service,
+            config: self.clone(),
+        })
+    }
+}
+
+pub struct ErrorPagesService<S> {
+    pub(crate) service: S,
+    pub(crate) config: ErrorPages,
+}
+
+impl<S, B> Service<ServiceRequest> for ErrorPagesService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let fut = self.service.call(req);
+        let config = self.config.clone();
+
+        Box::pin(async move {
+            let res = fut.await?;
+            let status = res.status();
+
+            // Leave pages that are already Inertia responses alone.
+            if !config.statuses.contains(&status)
+                || res.response().headers().contains_key(X_INERTIA)
+            {
+                return Ok(res.map_into_left_body());
+            }
+
+            let message = config.message(status, res.response().error());
+            let (req, res) = res.into_parts();
+            let page = Inertia::new(
+                config.component.clone(),
+                serde_json::json!({
+                    "status": status.as_u16(),
+                    "message": message,
+                }),
+                req.uri().to_string(),
+            );
+
+            let mut response = page.render_response(&req);
+            *response.status_mut() = status;
+            // Keep what the error carried besides its body, e.g. the fresh `XSRF-TOKEN` cookie of
+            // a `419`, `Retry-After` on a `503` or `WWW-Authenticate` on a `403`.
+            for (name, value) in res.headers() {
+                if name != http::header::CONTENT_TYPE && name != http::header::CONTENT_LENGTH {
+                    response.headers_mut().append(name.clone(), value.clone());
+                }
+            }
+            Ok(ServiceResponse::new(req, response).map_into_right_body())
+        })
+    }
+}
@@ -0,0 +1,392 @@
+use crate::cache::CacheControl;
+use crate::config::SsrConfig;
+use crate::error::InertiaError;
+use crate::metrics::InertiaMetrics;
+use crate::pages::{MissingComponent, PagesDirectory};
+use crate::root_view::RootView;
+use crate::size::{self, Oversized, SizeLimit};
+use crate::Inertia;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+pub(crate) type VersionFn = Arc<dyn Fn() -> String + Send + Sync>;
+
+#[derive(Clone)]
+pub struct ResponseFactory {
+    root_view: RootView,
+    root_views: BTreeMap<String, RootView>,
+    shared_props: Arc<Mutex<serde_json::Value>>,
+    version: Option<VersionFn>,
+    encrypt_history: bool,
+    clear_history: bool,
+    pages: Option<PagesDirectory>,
+    dev_mode: bool,
+    errors_key: String,
+    ssr: Option<SsrConfig>,
+    prefetch_cache: Option<CacheControl>,
+    etags: bool,
+    html_cache: Option<CacheControl>,
+    json_cache: Option<CacheControl>,
+    diagnostics: bool,
+    log_props: bool,
+    size_limit: Option<SizeLimit>,
+    metrics: Arc<InertiaMetrics>,
+}
+
+impl Default for ResponseFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResponseFactory {
+    pub fn new() -> Self {
+        Self {
+            root_view: RootView::new("app"),
+            root_views: BTreeMap::new(),
+            shared_props: Arc::new(Mutex::new(serde_json::Value::Object(Default::default()))),
+            version: None,
+            encrypt_history: false,
+            clear_history: false,
+            pages: None,
+            dev_mode: cfg!(debug_assertions),
+            errors_key: "errors".to_string(),
+            ssr: None,
+            prefetch_cache: None,
+            etags: true,
+            html_cache: None,
+            json_cache: None,
+            diagnostics: false,
+            log_props: false,
+            size_limit: None,
+            metrics: Arc::default(),
+        }
+    }
+
+    pub fn set_root_view(&mut self, name: &str) {
+        self.root_view.set_path(name);
+    }
+
+    /// Registers a root view that scopes select with
+    /// [`RootViewMiddleware`](crate::RootViewMiddleware) and responses with `.root_view(name)`.
+    pub fn add_root_view(&mut self, name: &str, view: RootView) {
+        self.root_views.insert(name.to_string(), view);
+    }
+
+    pub fn get_root_view(&self, name: &str) -> Option<&RootView> {
+        self.root_views.get(name)
+    }
+
+    /// Whether `version` is the factory's version or that of one of its root views.
+    pub fn accepts_version(&self, version: Option<&str>) -> bool {
+        version == Some(self.get_version().as_str()) || self.is_root_view_version(version)
+    }
+
+    /// Whether `version` is the version of one of the named root views.
+    pub fn is_root_view_version(&self, version: Option<&str>) -> bool {
+        self.root_views
+            .values()
+            .filter_map(RootView::get_version)
+            .any(|v| version == Some(v.as_str()))
+    }
+
+    pub fn render_root(&self, data_page: &str) -> Option<String> {
+        self.try_render_root(data_page).ok().flatten()
+    }
+
+    /// Renders the root view, or `Ok(None)` when there is no root view file to render.
+    pub fn try_render_root(&self, data_page: &str) -> Result<Option<String>, InertiaError> {
+        self.root_view.render(data_page, "")
+    }
+
+    /// The default root view, or the named one.
+    pub(crate) fn select_root_view(&self, name: Option<&str>) -> Result<&RootView, InertiaError> {
+        match name {
+            Some(name) => self
+                .get_root_view(name)
+                .ok_or_else(|| InertiaError::Template(format!("unknown root view `{name}`"))),
+            None => Ok(&self.root_view),
+        }
+    }
+
+    pub fn share(&self, key: &str, value: serde_json::Value) {
+        let mut shared_props = self.shared_props.lock().unwrap();
+        shared_props[key] = value;
+    }
+
+    pub fn get_shared(&self, key: Option<&str>) -> serde_json::Value {
+        let shared_props = self.shared_props.lock().unwrap();
+        match key {
+            Some(k) => shared_props
+                .get(k)
+                .cloned()
+                .unwrap_or(serde_json::Value::Null),
+            None => shared_props.clone(),
+        }
+    }
+
+    pub fn flush_shared(&self) {
+        let mut shared_props = self.shared_props.lock().unwrap();
+        *shared_props = serde_json::Value::Object(Default::default());
+    }
+
+    pub fn set_version<F>(&mut self, version: F)
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        self.version = Some(Arc::new(version));
+    }
+
+    /// Whether the factory or one of its root views has an asset version to check.
+    pub fn has_version(&self) -> bool {
+        self.version.is_some()
+            || self
+                .root_views
+                .values()
+                .any(|view| view.get_version().is_some())
+    }
+
+    pub fn get_version(&self) -> String {
+        match &self.version {
+            Some(version_fn) => version_fn(),
+            None => "".to_string(),
+        }
+    }
+
+    /// Encrypt the browser history state of every page rendered by this factory.
+    pub fn set_encrypt_history(&mut self, encrypt: bool) {
+        self.encrypt_history = encrypt;
+    }
+
+    pub fn encrypt_history(&self) -> bool {
+        self.encrypt_history
+    }
+
+    /// Clear the client's encrypted history on every page rendered by this factory.
+    pub fn set_clear_history(&mut self, clear: bool) {
+        self.clear_history = clear;
+    }
+
+    pub fn clear_history(&self) -> bool {
+        self.clear_history
+    }
+
+    /// Checks rendered component names against the frontend pages directory in debug builds.
+    pub fn set_pages_directory(&mut self, pages: PagesDirectory) {
+        self.pages = Some(pages);
+    }
+
+    pub fn pages_directory(&self) -> Option<&PagesDirectory> {
+        self.pages.as_ref()
+    }
+
+    /// Turns on development checks such as missing page components. Defaults to debug builds.
+    pub fn set_dev_mode(&mut self, dev_mode: bool) {
+        self.dev_mode = dev_mode;
+    }
+
+    pub fn dev_mode(&self) -> bool {
+        self.dev_mode
+    }
+
+    /// The prop that flashed validation errors are merged into. Defaults to `errors`.
+    pub fn set_errors_key(&mut self, key: &str) {
+        self.errors_key = key.to_string();
+    }
+
+    pub fn errors_key(&self) -> &str {
+        &self.errors_key
+    }
+
+    pub fn set_ssr(&mut self, ssr: SsrConfig) {
+        self.ssr = Some(ssr);
+    }
+
+    pub fn ssr(&self) -> Option<&SsrConfig> {
+        self.ssr.as_ref()
+    }
+
+    /// The `Cache-Control` sent with responses to prefetch requests, or `None` to send none.
+    /// Off by default; `private, max-age=30` matches the client's own prefetch cache time.
+    /// Cacheable prefetch responses also vary on `Purpose` and `Sec-Purpose`.
+    pub fn set_prefetch_cache(&mut self, cache: Option<CacheControl>) {
+        self.prefetch_cache = cache;
+    }
+
+    pub fn prefetch_cache(&self) -> Option<&CacheControl> {
+        self.prefetch_cache.as_ref()
+    }
+
+    /// Send an `ETag` with Inertia visits and answer a matching `If-None-Match` with
+    /// `304 Not Modified`, e.g. for polling partial reloads. On by default.
+    pub fn set_etags(&mut self, etags: bool) {
+        self.etags = etags;
+    }
+
+    pub fn etags(&self) -> bool {
+        self.etags
+    }
+
+    /// The `Cache-Control` sent with full page loads, i.e. the HTML shell. Sends none by default.
+    pub fn set_html_cache(&mut self, cache: Option<CacheControl>) {
+        self.html_cache = cache;
+    }
+
+    pub fn html_cache(&self) -> Option<&CacheControl> {
+        self.html_cache.as_ref()
+    }
+
+    /// The `Cache-Control` sent with the JSON page objects of Inertia visits. Sends none by
+    /// default.
+    pub fn set_json_cache(&mut self, cache: Option<CacheControl>) {
+        self.json_cache = cache;
+    }
+
+    pub fn json_cache(&self) -> Option<&CacheControl> {
+        self.json_cache.as_ref()
+    }
+
+    /// Echoes the partial reload headers back on Inertia visits and lists the props that were
+    /// left out, see [`Diagnostics`](crate::Diagnostics). Off by default, also in dev mode, as
+    /// the headers reflect client input.
+    pub fn set_diagnostics(&mut self, diagnostics: bool) {
+        self.diagnostics = diagnostics;
+    }
+
+    pub fn diagnostics(&self) -> bool {
+        self.diagnostics
+    }
+
+    /// Logs the props of every page at `debug` level with the `tracing` feature. Off by
+    /// default, as props often hold personal data.
+    pub fn set_log_props(&mut self, log_props: bool) {
+        self.log_props = log_props;
+    }
+
+    pub fn log_props(&self) -> bool {
+        self.log_props
+    }
+
+    /// Limits the serialized size of page objects, see [`SizeLimit`].
+    pub fn set_size_limit(&mut self, limit: SizeLimit) {
+        self.size_limit = Some(limit);
+    }
+
+    pub fn size_limit(&self) -> Option<&SizeLimit> {
+        self.size_limit.as_ref()
+    }
+
+    pub fn metrics(&self) -> &InertiaMetrics {
+        &self.metrics
+    }
+
+    /// Counts a `409 Conflict` for a stale asset version.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn record_version_conflict(&self, url: &str, version: Option<&str>) {
+        trace_span!("inertia.version_conflict", url, client_version = version);
+        trace_event!(
+            info,
+            "stale asset version, asking the client for a full page load"
+        );
+        self.metrics.add_version_conflict();
+    }
+
+    /// Reports a page object of `size` bytes that is over the size limit. Returns the biggest
+    /// props for the `X-Inertia-Debug-Prop-Sizes` header when dev mode asks for it, and fails
+    /// outside dev mode when the limit rejects oversized pages.
+    pub(crate) fn check_size(
+        &self,
+        component: &str,
+        size: usize,
+        props: &serde_json::Value,
+    ) -> Result<Option<Vec<(String, usize)>>, InertiaError> {
+        let Some(limit) = self.size_limit.as_ref() else {
+            return Ok(None);
+        };
+        if size <= limit.bytes() {
+            return Ok(None);
+        }
+
+        let sizes = size::prop_sizes(props);
+        let message = format!(
+            "Inertia page `{component}` is {size} bytes, over the limit of {} bytes, biggest props: {}",
+            limit.bytes(),
+            size::format_sizes(&sizes)
+        );
+        if self.dev_mode {
+            report!(warn, "{message}");
+            return Ok(limit.reports_header().then_some(sizes));
+        }
+        match limit.oversized() {
+            Oversized::Warn => {
+                report!(warn, "{message}");
+                Ok(None)
+            }
+            Oversized::Reject => Err(InertiaError::TooLarge(message)),
+        }
+    }
+
+    /// Fails when `component` is missing from the pages directory and that is configured to fail.
+    pub(crate) fn check_component(&self, component: &str) -> Result<(), InertiaError> {
+        if !self.dev_mode {
+            return Ok(());
+        }
+        let Some(pages) = self.pages.as_ref() else {
+            return Ok(());
+        };
+        if pages.exists(component) {
+            return Ok(());
+        }
+
+        let message = format!(
+            "Inertia page component `{}` was not found in {}",
+            component,
+            pages.dir().display()
+        );
+        match pages.missing_component() {
+            MissingComponent::Warn => {
+                report!(warn, "{message}");
+                Ok(())
+            }
+            MissingComponent::Fail => Err(InertiaError::MissingComponent(message)),
+        }
+    }
+
+    /// Merges the shared props into `props`.
+    ///
+    /// A serialization failure is reported when the page is turned into a response.
+    pub fn render<T: Serialize>(
+        &self,
+        component: &str,
+        props: T,
+        url: &str,
+    ) -> Inertia<serde_json::Value> {
+        self.try_render(component, props, url)
+            .unwrap_or_else(|err| {
+                let mut inertia = Inertia::new(
+                    component.to_string(),
+                    serde_json::Value::Null,
+                    url.to_string(),
+                );
+                inertia.error = Some(err);
+                inertia
+            })
+    }
+
+    pub fn try_render<T: Serialize>(
+        &self,
+        component: &str,
+        props: T,
+        url: &str,
+    ) -> Result<Inertia<serde_json::Value>, InertiaError> {
+        let shared_props = self.get_shared(None);
+        let mut props = serde_json::to_value(props)?;
+        if let serde_json::Value::Object(ref mut p) = props {
+            if let serde_json::Value::Object(ref s) = shared_props {
+                p.extend(s.clone());
+            }
+        }
+        Ok(Inertia::new(component.to_string(), props, url.to_string()))
+    }
+}
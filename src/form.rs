@@ -0,0 +1,334 @@
+use crate::validation::ValidationErrors;
+use actix_multipart::Multipart;
+use actix_service::{forward_ready, Service, Transform};
+use actix_web::body::EitherBody;
+use actix_web::dev::Payload;
+use actix_web::error::{
+    ErrorPayloadTooLarge, ErrorUnsupportedMediaType, InternalError, PayloadError,
+};
+use actix_web::web::{Bytes, BytesMut};
+use actix_web::{
+    dev::ServiceRequest, dev::ServiceResponse, http, Error, FromRequest, HttpMessage, HttpRequest,
+    Result,
+};
+use futures::future::{ok, Ready};
+use futures::stream::{self, Stream, StreamExt};
+use futures_util::future::LocalBoxFuture;
+use serde::de::DeserializeOwned;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::rc::Rc;
+
+/// Form field HTML forms use to send methods other than `GET` and `POST`.
+pub static METHOD_FIELD: &str = "_method";
+
+/// Rewrites `POST` requests to the method named by a `_method` field in an urlencoded or
+/// multipart body, so `<input type="hidden" name="_method" value="PUT">` routes to a `PUT`
+/// handler. Only `PUT`, `PATCH` and `DELETE` can be spoofed.
+///
+/// Wrap the `App` with this middleware so the method is rewritten before routing.
+///
+/// At most [`limit`](MethodSpoofingMiddleware::limit) bytes are buffered: larger urlencoded
+/// bodies are rejected with `413 Payload Too Large`, multipart bodies are only searched for
+/// `_method` up to the limit and streamed on unchanged, so file uploads are not buffered.
+#[derive(Clone)]
+pub struct MethodSpoofingMiddleware {
+    limit: usize,
+}
+
+impl Default for MethodSpoofingMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MethodSpoofingMiddleware {
+    pub fn new() -> Self {
+        Self {
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    /// The most bytes read to find the `_method` field. Defaults to 256 KiB, the default of
+    /// actix-web's `PayloadConfig`.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+const DEFAULT_LIMIT: usize = 256 * 1024;
+
+impl<S, B> Transform<S, ServiceRequest> for MethodSpoofingMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = MethodSpoofingMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(MethodSpoofingMiddlewareService {
+            service: Rc::new(service),
+            limit: self.limit,
+        })
+    }
+}
+
+pub struct MethodSpoofingMiddlewareService<S> {
+    service: Rc<S>,
+    limit: usize,
+}
+
+impl<S, B> Service<ServiceRequest> for MethodSpoofingMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let limit = self.limit;
+
+        let kind = form_kind(req.request());
+        if req.method() != http::Method::POST
+            || !matches!(kind, Some(FormKind::UrlEncoded | FormKind::Multipart))
+        {
+            let fut = service.call(req);
+            return Box::pin(async move { Ok(fut.await?.map_into_left_body()) });
+        }
+
+        Box::pin(async move {
+            let mut payload = req.take_payload();
+            let mut prefix = BytesMut::new();
+            let mut spoofed = None;
+
+            while let Some(chunk) = payload.next().await {
+                prefix.extend_from_slice(&chunk?);
+                if kind == Some(FormKind::UrlEncoded) {
+                    if prefix.len() > limit {
+                        let err = ErrorPayloadTooLarge("form body is too large");
+                        return Ok(req.error_response(err).map_into_right_body());
+                    }
+                    continue;
+                }
+
+                let scanned = prefix.clone().freeze().slice(..prefix.len().min(limit));
+                spoofed = multipart_method(req.request(), scanned).await;
+                if spoofed.is_some() || prefix.len() >= limit {
+                    break;
+                }
+            }
+            let prefix = prefix.freeze();
+
+            if kind == Some(FormKind::UrlEncoded) {
+                spoofed = form_urlencoded::parse(&prefix)
+                    .find(|(name, _)| name == METHOD_FIELD)
+                    .and_then(|(_, value)| spoofable_method(&value));
+            }
+            if let Some(method) = spoofed {
+                req.head_mut().method = method;
+            }
+
+            req.set_payload(Payload::from(Box::pin(
+                stream::once(async move { Ok(prefix) }).chain(payload),
+            ) as BytesStream));
+            Ok(service.call(req).await?.map_into_left_body())
+        })
+    }
+}
+
+/// The spoofed method in a multipart body read up to `prefix`, once its `_method` field is
+/// complete.
+async fn multipart_method(req: &HttpRequest, prefix: Bytes) -> Option<http::Method> {
+    let mut multipart = Multipart::new(req.headers(), bytes_stream(prefix));
+    while let Some(Ok(mut field)) = multipart.next().await {
+        let is_method = field.name() == METHOD_FIELD;
+        let mut value = BytesMut::new();
+        while let Some(chunk) = field.next().await {
+            value.extend_from_slice(&chunk.ok()?);
+        }
+        if is_method {
+            return spoofable_method(&String::from_utf8_lossy(&value));
+        }
+    }
+    None
+}
+
+fn spoofable_method(value: &str) -> Option<http::Method> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "PUT" => Some(http::Method::PUT),
+        "PATCH" => Some(http::Method::PATCH),
+        "DELETE" => Some(http::Method::DELETE),
+        _ => None,
+    }
+}
+
+/// Form data sent by an Inertia `useForm` submission.
+///
+/// Accepts JSON bodies, which Inertia sends by default, as well as multipart and urlencoded
+/// bodies. Multipart and urlencoded fields are flat, so nested `user[name]` keys are not
+/// expanded and file parts are skipped.
+///
+/// When the body does not deserialize into `T` the request is redirected back with the
+/// offending fields in the `errors` prop instead of answering `400 Bad Request`.
+pub struct InertiaForm<T>(pub T);
+
+impl<T> InertiaForm<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for InertiaForm<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for InertiaForm<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: DeserializeOwned + 'static> FromRequest for InertiaForm<T> {
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let body = Bytes::from_request(&req, payload);
+
+        Box::pin(async move {
+            let body = body.await?;
+            match parse_form(&req, body).await? {
+                Ok(value) => Ok(InertiaForm(value)),
+                Err(errors) => Err(InternalError::from_response(
+                    "invalid form data",
+                    errors.redirect_back(&req),
+                )
+                .into()),
+            }
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FormKind {
+    Json,
+    Multipart,
+    UrlEncoded,
+}
+
+fn form_kind(req: &HttpRequest) -> Option<FormKind> {
+    match req.content_type().trim().to_ascii_lowercase().as_str() {
+        "application/json" => Some(FormKind::Json),
+        "application/x-www-form-urlencoded" => Some(FormKind::UrlEncoded),
+        "multipart/form-data" => Some(FormKind::Multipart),
+        _ => None,
+    }
+}
+
+/// Deserializes a form body into `T`, turning deserialization failures into field errors.
+///
+/// The outer error is for bodies that cannot be read at all, e.g. an unsupported content type.
+pub(crate) async fn parse_form<T: DeserializeOwned>(
+    req: &HttpRequest,
+    body: Bytes,
+) -> Result<Result<T, ValidationErrors>> {
+    let kind = form_kind(req).ok_or_else(|| {
+        ErrorUnsupportedMediaType("expected a JSON, multipart or urlencoded body")
+    })?;
+
+    let result = match kind {
+        FormKind::Json => match serde_json::from_slice::<serde_json::Value>(&body) {
+            Ok(value) => serde_path_to_error::deserialize(value).map_err(field_errors),
+            Err(_) => Err(ValidationErrors::from_iter([(
+                "form",
+                "The request body is not valid JSON.",
+            )])),
+        },
+        FormKind::Multipart | FormKind::UrlEncoded => {
+            let fields = form_fields(req, body).await?;
+            let encoded = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&fields)
+                .finish();
+            let deserializer =
+                serde_urlencoded::Deserializer::new(form_urlencoded::parse(encoded.as_bytes()));
+            serde_path_to_error::deserialize(deserializer).map_err(field_errors)
+        }
+    };
+
+    Ok(result)
+}
+
+/// The text fields of an urlencoded or multipart body, in order.
+async fn form_fields(req: &HttpRequest, body: Bytes) -> Result<Vec<(String, String)>> {
+    if form_kind(req) != Some(FormKind::Multipart) {
+        return Ok(form_urlencoded::parse(&body).into_owned().collect());
+    }
+
+    let mut fields = Vec::new();
+    let mut multipart = Multipart::new(req.headers(), bytes_stream(body));
+    while let Some(field) = multipart.next().await {
+        let mut field = field?;
+        let is_file = field.content_disposition().get_filename().is_some();
+        let name = field.name().to_string();
+
+        let mut value = BytesMut::new();
+        while let Some(chunk) = field.next().await {
+            value.extend_from_slice(&chunk?);
+        }
+
+        if !is_file {
+            fields.push((name, String::from_utf8_lossy(&value).into_owned()));
+        }
+    }
+    Ok(fields)
+}
+
+fn field_errors<E: std::fmt::Display>(err: serde_path_to_error::Error<E>) -> ValidationErrors {
+    let path = err.path().to_string();
+    let message = err.inner().to_string();
+
+    let (field, message) = match message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.strip_suffix('`'))
+    {
+        Some(missing) => (
+            join_path(&path, missing),
+            format!("The {missing} field is required."),
+        ),
+        None if path == "." => ("form".to_string(), message),
+        None => (path, message),
+    };
+
+    ValidationErrors::from_iter([(field, message)])
+}
+
+fn join_path(path: &str, field: &str) -> String {
+    if path == "." {
+        field.to_string()
+    } else {
+        format!("{path}.{field}")
+    }
+}
+
+type BytesStream = Pin<Box<dyn Stream<Item = Result<Bytes, PayloadError>>>>;
+
+fn bytes_stream(body: Bytes) -> BytesStream {
+    Box::pin(stream::once(async move { Ok(body) }))
+}
@@ -0,0 +1,13 @@
+//! A content hash that stays the same across builds and Rust releases, unlike
+//! `std`'s `DefaultHasher`, for values clients compare between deployments.
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a of `bytes` as 16 hex digits.
+pub(crate) fn stable_hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("{hash:016x}")
+}
@@ -0,0 +1,170 @@
+//! `<title>` and meta tags for full page loads, rendered into the `{{HEAD}}` placeholder of
+//! the root view.
+//!
+//! ```ignore
+//! Inertia::new("Posts/Show".to_string(), props, url).head(
+//!     Head::new()
+//!         .title(&post.title)
+//!         .meta("description", &post.summary)
+//!         .og("image", &post.image_url)
+//!         .canonical(&post.url),
+//! )
+//! ```
+
+use serde_json::Value;
+
+/// Tags for the document head of a page.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Head {
+    title: Option<String>,
+    meta: Vec<Meta>,
+    canonical: Option<String>,
+    json_ld: Vec<Value>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Meta {
+    attribute: &'static str,
+    key: String,
+    content: String,
+}
+
+impl Head {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Adds `<meta name="{name}" content="{content}">`, replacing an earlier one with the same name.
+    pub fn meta(self, name: &str, content: &str) -> Self {
+        self.push_meta("name", name, content)
+    }
+
+    /// Adds `<meta property="{property}" content="{content}">`.
+    pub fn property(self, property: &str, content: &str) -> Self {
+        self.push_meta("property", property, content)
+    }
+
+    /// Adds an Open Graph tag, e.g. `og("image", url)` for `og:image`.
+    pub fn og(self, property: &str, content: &str) -> Self {
+        self.push_meta("property", &format!("og:{property}"), content)
+    }
+
+    pub fn canonical(mut self, url: &str) -> Self {
+        self.canonical = Some(url.to_string());
+        self
+    }
+
+    /// Adds a `<script type="application/ld+json">` block with structured data.
+    pub fn json_ld(mut self, data: Value) -> Self {
+        self.json_ld.push(data);
+        self
+    }
+
+    fn push_meta(mut self, attribute: &'static str, key: &str, content: &str) -> Self {
+        self.meta
+            .retain(|meta| !(meta.attribute == attribute && meta.key == key));
+        self.meta.push(Meta {
+            attribute,
+            key: key.to_string(),
+            content: content.to_string(),
+        });
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The HTML tags, one per line.
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags = Vec::new();
+        if let Some(title) = &self.title {
+            tags.push(format!("<title>{}</title>", escape(title)));
+        }
+        for meta in &self.meta {
+            tags.push(format!(
+                "<meta {}=\"{}\" content=\"{}\">",
+                meta.attribute,
+                escape(&meta.key),
+                escape(&meta.content)
+            ));
+        }
+        if let Some(canonical) = &self.canonical {
+            tags.push(format!(
+                "<link rel=\"canonical\" href=\"{}\">",
+                escape(canonical)
+            ));
+        }
+        for data in &self.json_ld {
+            // `</script>` inside a string would end the block early.
+            let json = data.to_string().replace("</", "<\\/");
+            tags.push(format!(
+                "<script type=\"application/ld+json\">{json}</script>"
+            ));
+        }
+        tags
+    }
+
+    pub fn render(&self) -> String {
+        self.tags().join("\n")
+    }
+
+    /// Combines these tags with the `head` tags returned by an SSR server.
+    ///
+    /// A tag from `ssr_head` replaces the tag from this head that has the same title, meta
+    /// name or property, or canonical link, so nothing is sent twice.
+    pub fn merge(&self, ssr_head: &[String]) -> String {
+        let ssr_keys: Vec<String> = ssr_head.iter().filter_map(|tag| tag_key(tag)).collect();
+
+        self.tags()
+            .into_iter()
+            .filter(|tag| match tag_key(tag) {
+                Some(key) => !ssr_keys.contains(&key),
+                None => !ssr_head.contains(tag),
+            })
+            .chain(ssr_head.iter().cloned())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// What makes a head tag unique, or `None` for tags that may repeat.
+fn tag_key(tag: &str) -> Option<String> {
+    let tag = tag.trim_start();
+    if tag.starts_with("<title") {
+        return Some("title".to_string());
+    }
+    if tag.starts_with("<meta") {
+        if attribute_value(tag, "charset").is_some() {
+            return Some("meta charset".to_string());
+        }
+        for attribute in ["name", "property", "http-equiv"] {
+            if let Some(value) = attribute_value(tag, attribute) {
+                return Some(format!("meta {attribute}={value}"));
+            }
+        }
+    }
+    if tag.starts_with("<link") && attribute_value(tag, "rel").as_deref() == Some("canonical") {
+        return Some("canonical".to_string());
+    }
+    None
+}
+
+fn attribute_value(tag: &str, attribute: &str) -> Option<String> {
+    let start = tag.find(&format!(" {attribute}=\""))? + attribute.len() + 3;
+    let end = start + tag[start..].find('"')?;
+    Some(tag[start..end].to_string())
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -1,11 +1,18 @@
 use actix_web::{HttpRequest, HttpResponse};
 use serde::Serialize;
 
-use crate::Inertia;
+use crate::props::{prop_fn, PropFn};
+use crate::{Head, Inertia, PropOptions};
 
 pub struct InertiaResponder<T: Serialize> {
     component: String,
     props: T,
+    encrypt_history: Option<bool>,
+    clear_history: Option<bool>,
+    options: PropOptions,
+    root_view: Option<String>,
+    head: Option<Head>,
+    resolvers: Vec<(String, PropFn)>,
 }
 
 impl<T: Serialize> InertiaResponder<T> {
@@ -13,11 +20,94 @@ impl<T: Serialize> InertiaResponder<T> {
         Self {
             component: component.into(),
             props,
+            encrypt_history: None,
+            clear_history: None,
+            options: PropOptions::default(),
+            root_view: None,
+            head: None,
+            resolvers: Vec::new(),
         }
     }
 
+    pub fn encrypt_history(mut self, encrypt: bool) -> Self {
+        self.encrypt_history = Some(encrypt);
+        self
+    }
+
+    pub fn clear_history(mut self) -> Self {
+        self.clear_history = Some(true);
+        self
+    }
+
+    pub fn root_view(mut self, name: &str) -> Self {
+        self.root_view = Some(name.to_string());
+        self
+    }
+
+    pub fn head(mut self, head: Head) -> Self {
+        self.head = Some(head);
+        self
+    }
+
+    pub fn lazy(mut self, key: &str) -> Self {
+        self.options = self.options.lazy(key);
+        self
+    }
+
+    /// Like [`lazy`](InertiaResponder::lazy), with a value computed only when it is asked for.
+    pub fn lazy_with<F, V>(mut self, key: &str, resolve: F) -> Self
+    where
+        F: FnOnce() -> V + Send + 'static,
+        V: Serialize,
+    {
+        self.options = self.options.lazy(key);
+        self.resolvers.push((key.to_string(), prop_fn(resolve)));
+        self
+    }
+
+    pub fn always(mut self, key: &str) -> Self {
+        self.options = self.options.always(key);
+        self
+    }
+
+    pub fn defer(mut self, key: &str, group: &str) -> Self {
+        self.options = self.options.defer(key, group);
+        self
+    }
+
+    /// Like [`defer`](InertiaResponder::defer), with a value computed only when its group is
+    /// loaded.
+    pub fn defer_with<F, V>(mut self, key: &str, group: &str, resolve: F) -> Self
+    where
+        F: FnOnce() -> V + Send + 'static,
+        V: Serialize,
+    {
+        self.options = self.options.defer(key, group);
+        self.resolvers.push((key.to_string(), prop_fn(resolve)));
+        self
+    }
+
+    pub fn merge(mut self, key: &str) -> Self {
+        self.options = self.options.merge(key);
+        self
+    }
+
     pub async fn respond_to(self, req: &HttpRequest) -> HttpResponse {
-        let inertia = Inertia::new(self.component, self.props, req.uri().to_string());
+        let mut inertia = Inertia::new(self.component, self.props, req.uri().to_string())
+            .prop_options(self.options);
+        inertia.resolvers = self.resolvers;
+        if let Some(encrypt) = self.encrypt_history {
+            inertia = inertia.encrypt_history(encrypt);
+        }
+        if self.clear_history == Some(true) {
+            inertia = inertia.clear_history();
+        }
+        if let Some(root_view) = &self.root_view {
+            inertia = inertia.root_view(root_view);
+        }
+        if let Some(head) = self.head {
+            inertia = inertia.head(head);
+        }
 
         inertia.into_response(req).await
     }
@@ -0,0 +1,239 @@
+//! Everything an Inertia app needs, registered with a single `wrap`.
+//!
+//! ```ignore
+//! let factory = InertiaConfig::from_env()?.build()?;
+//!
+//! HttpServer::new(move || {
+//!     App::new()
+//!         .wrap(
+//!             InertiaLayer::new(factory.clone())
+//!                 .share(|req| json!({ "appName": "Demo" }))
+//!                 .error_pages(ErrorPages::new("Error")),
+//!         )
+//!         .route("/", web::get().to(index))
+//! })
+//! ```
+//!
+//! Actix's `App::configure` cannot add middleware, which is why this is a layer rather than
+//! a configure function.
+
+use crate::actix::{ResponseFactory, VersionMiddlewareService};
+use crate::error_pages::{ErrorPages, ErrorPagesService};
+use crate::X_INERTIA;
+use actix_service::boxed::{self, BoxService};
+use actix_service::{forward_ready, Service, ServiceExt, Transform};
+use actix_web::body::MessageBody;
+use actix_web::dev::{Extensions, ServiceRequest, ServiceResponse};
+use actix_web::{http, web, Error, HttpMessage, HttpRequest, Result};
+use futures::future::{ok, Ready};
+use futures_util::future::LocalBoxFuture;
+use serde_json::{Map, Value};
+use std::rc::Rc;
+use std::sync::Arc;
+
+type ShareFn = Arc<dyn Fn(&HttpRequest) -> Value + Send + Sync>;
+
+/// Request-scoped props added by [`InertiaLayer::share`]. Page props with the same key win.
+pub(crate) struct SharedProps(pub(crate) Map<String, Value>);
+
+/// Registers the [`ResponseFactory`] and the Inertia middleware in the right order.
+///
+/// From the outside in: the factory is made available to handlers as
+/// `web::Data<ResponseFactory>`, error responses are rendered by [`ErrorPages`], stale asset
+/// versions get a `409`, shared props are computed, and `302` redirects after `PUT`, `PATCH`
+/// and `DELETE` visits become `303` so the browser follows them with a `GET`.
+///
+/// Every part except the error pages is on by default and can be turned off.
+#[derive(Clone)]
+pub struct InertiaLayer {
+    factory: web::Data<ResponseFactory>,
+    register_factory: bool,
+    version_check: bool,
+    see_other_redirects: bool,
+    shared: Vec<ShareFn>,
+    error_pages: Option<ErrorPages>,
+}
+
+impl InertiaLayer {
+    pub fn new(factory: ResponseFactory) -> Self {
+        Self::from_data(web::Data::new(factory))
+    }
+
+    /// Uses a factory that is already shared, e.g. with other apps or tests.
+    pub fn from_data(factory: web::Data<ResponseFactory>) -> Self {
+        Self {
+            factory,
+            register_factory: true,
+            version_check: true,
+            see_other_redirects: true,
+            shared: Vec::new(),
+            error_pages: None,
+        }
+    }
+
+    /// Whether to register the factory as app data. Turn off when the app already does.
+    pub fn register_factory(mut self, register: bool) -> Self {
+        self.register_factory = register;
+        self
+    }
+
+    /// Whether to answer Inertia visits with an outdated asset version with `409 Conflict`.
+    /// Only applies when the factory or one of its root views has a version; any of those
+    /// versions is accepted here, and pages check the version of their own root view. Turning
+    /// this off also turns off the check pages make.
+    pub fn version_check(mut self, check: bool) -> Self {
+        self.version_check = check;
+        self
+    }
+
+    /// Whether to turn `302` redirects after `PUT`, `PATCH` and `DELETE` visits into `303`.
+    pub fn see_other_redirects(mut self, see_other: bool) -> Self {
+        self.see_other_redirects = see_other;
+        self
+    }
+
+    /// Adds props computed per request, e.g. the current user, to every page.
+    ///
+    /// `share` must return a JSON object. It can be called several times.
+    pub fn share<F>(mut self, share: F) -> Self
+    where
+        F: Fn(&HttpRequest) -> Value + Send + Sync + 'static,
+    {
+        self.shared.push(Arc::new(share));
+        self
+    }
+
+    /// Renders error responses as Inertia pages.
+    pub fn error_pages(mut self, error_pages: ErrorPages) -> Self {
+        self.error_pages = Some(error_pages);
+        self
+    }
+}
+
+type InnerService = BoxService<ServiceRequest, ServiceResponse, Error>;
+
+impl<S, B> Transform<S, ServiceRequest> for InertiaLayer
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Transform = InertiaLayerService;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        let mut service: InnerService = boxed::service(PrepareService {
+            service,
+            shared: self.shared.clone(),
+            see_other_redirects: self.see_other_redirects,
+        });
+
+        // Without a version there is nothing to conflict with.
+        if self.version_check && self.factory.has_version() {
+            let factory = self.factory.clone();
+            service = boxed::service(
+                VersionMiddlewareService {
+                    service,
+                    accepts: Arc::new(move |version| factory.accepts_version(version)),
+                }
+                .map(ServiceResponse::map_into_boxed_body),
+            );
+        }
+
+        if let Some(error_pages) = &self.error_pages {
+            service = boxed::service(
+                ErrorPagesService {
+                    service,
+                    config: error_pages.clone(),
+                }
+                .map(ServiceResponse::map_into_boxed_body),
+            );
+        }
+
+        let mut data = Extensions::new();
+        if self.register_factory {
+            data.insert(self.factory.clone());
+        }
+        if !self.version_check {
+            data.insert(SkipVersionCheck);
+        }
+
+        ok(InertiaLayerService {
+            service,
+            data: Rc::new(data),
+        })
+    }
+}
+
+pub struct InertiaLayerService {
+    service: InnerService,
+    data: Rc<Extensions>,
+}
+
+impl Service<ServiceRequest> for InertiaLayerService {
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        req.add_data_container(Rc::clone(&self.data));
+        self.service.call(req)
+    }
+}
+
+/// App data marker for a layer with the version check turned off.
+pub(crate) struct SkipVersionCheck;
+
+/// Computes the shared props and fixes redirect statuses, closest to the handler.
+struct PrepareService<S> {
+    service: S,
+    shared: Vec<ShareFn>,
+    see_other_redirects: bool,
+}
+
+impl<S, B> Service<ServiceRequest> for PrepareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        if !self.shared.is_empty() {
+            trace_span!("inertia.shared_props", count = self.shared.len());
+            let mut props = Map::new();
+            for share in &self.shared {
+                if let Value::Object(values) = share(req.request()) {
+                    props.extend(values);
+                }
+            }
+            req.extensions_mut().insert(SharedProps(props));
+        }
+
+        let see_other = self.see_other_redirects
+            && req.headers().contains_key(X_INERTIA)
+            && matches!(
+                *req.method(),
+                http::Method::PUT | http::Method::PATCH | http::Method::DELETE
+            );
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let mut res = fut.await?;
+            if see_other && res.status() == http::StatusCode::FOUND {
+                *res.response_mut().status_mut() = http::StatusCode::SEE_OTHER;
+            }
+            Ok(res.map_into_boxed_body())
+        })
+    }
+}
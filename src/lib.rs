@@ -1,6 +1,77 @@
+#[macro_use]
+mod trace;
+
+#[cfg(feature = "actix")]
 pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+pub mod cache;
+pub mod config;
+#[cfg(feature = "actix")]
+pub mod csrf;
+pub mod error;
+#[cfg(feature = "actix")]
+pub mod error_pages;
+pub mod factory;
+#[cfg(feature = "actix")]
+pub mod form;
+mod hash;
+pub mod head;
+#[cfg(feature = "actix")]
 pub mod inertia_responder;
-pub use actix::{example_handler, ResponseFactory, VersionMiddleware};
+#[cfg(feature = "actix")]
+pub mod layer;
+pub mod metrics;
+pub mod pages;
+#[cfg(feature = "actix")]
+pub mod precognition;
+pub mod props;
+pub mod protocol;
+pub mod root_view;
+pub mod size;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "typescript")]
+pub mod typescript;
+#[cfg(any(feature = "validator", feature = "garde"))]
+pub mod validated;
+#[cfg(feature = "actix")]
+pub mod validation;
+#[cfg(feature = "actix")]
+pub use actix::{example_handler, is_prefetch, EncryptHistoryMiddleware, VersionMiddleware};
+#[cfg(feature = "macros")]
+pub use actix_inertia_macros::InertiaPage;
+pub use cache::CacheControl;
+pub use config::{InertiaConfig, SsrConfig};
+#[cfg(feature = "actix")]
+pub use csrf::{CsrfFailure, CsrfMiddleware};
+pub use error::InertiaError;
+#[cfg(feature = "actix")]
+pub use error_pages::ErrorPages;
+pub use factory::ResponseFactory;
+#[cfg(feature = "actix")]
+pub use form::{InertiaForm, MethodSpoofingMiddleware};
+pub use head::Head;
+#[cfg(feature = "actix")]
+pub use layer::InertiaLayer;
+pub use metrics::InertiaMetrics;
+pub use pages::{MissingComponent, PagesDirectory};
+pub use props::{InertiaPage, PropOptions};
+pub use protocol::{etag, Diagnostics, InertiaRequest, Rendered};
+pub use root_view::RootView;
+#[cfg(feature = "actix")]
+pub use root_view::RootViewMiddleware;
+pub use size::{Oversized, SizeLimit};
+#[cfg(any(feature = "validator", feature = "garde"))]
+pub use validated::ValidatedInertiaForm;
+#[cfg(feature = "actix")]
+pub use validation::ValidationErrors;
+
+#[doc(hidden)]
+#[cfg(feature = "actix")]
+pub mod __private {
+    pub use actix_web;
+}
 
 pub static X_INERTIA: &str = "X-Inertia";
 pub static X_INERTIA_VERSION: &str = "X-Inertia-Version";
@@ -9,9 +80,21 @@ pub static X_INERTIA_ERROR_BAG: &str = "X-Inertia-Error-Bag";
 pub static X_INERTIA_PARTIAL_COMPONENT: &str = "X-Inertia-Partial-Component";
 pub static X_INERTIA_PARTIAL_ONLY: &str = "X-Inertia-Partial-Data";
 pub static X_INERTIA_PARTIAL_EXCEPT: &str = "X-Inertia-Partial-Except";
+pub static X_INERTIA_RESET: &str = "X-Inertia-Reset";
+pub static PURPOSE: &str = "Purpose";
+pub static SEC_PURPOSE: &str = "Sec-Purpose";
+pub static X_INERTIA_DEBUG_FILTERED_PROPS: &str = "X-Inertia-Debug-Filtered-Props";
+pub static X_INERTIA_DEBUG_PROP_SIZES: &str = "X-Inertia-Debug-Prop-Sizes";
 
 pub struct Inertia<T> {
     component: String,
     props: T,
     url: Option<String>,
+    encrypt_history: Option<bool>,
+    clear_history: Option<bool>,
+    options: PropOptions,
+    root_view: Option<String>,
+    head: Option<Head>,
+    error: Option<InertiaError>,
+    resolvers: Vec<(String, props::PropFn)>,
 }
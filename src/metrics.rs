@@ -0,0 +1,22 @@
+//! Counters for a metrics exporter to scrape.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counts events worth alerting on. Read them with
+/// [`ResponseFactory::metrics`](crate::ResponseFactory::metrics); clones of a factory share
+/// the same counters.
+#[derive(Debug, Default)]
+pub struct InertiaMetrics {
+    version_conflicts: AtomicU64,
+}
+
+impl InertiaMetrics {
+    /// Inertia visits answered with `409 Conflict` because of a stale asset version.
+    pub fn version_conflicts(&self) -> u64 {
+        self.version_conflicts.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn add_version_conflict(&self) {
+        self.version_conflicts.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+/// What to do when a rendered component has no file in the pages directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissingComponent {
+    /// Log a warning and render the page anyway.
+    Warn,
+    /// Answer `500 Internal Server Error` instead of rendering a blank page.
+    Fail,
+}
+
+/// The frontend directory page components are resolved against, e.g. `my-inertia-app/src/Pages`.
+///
+/// `Users/Show` resolves to `Users/Show.vue`, `Users/Show.jsx` and so on.
+#[derive(Clone, Debug)]
+pub struct PagesDirectory {
+    dir: PathBuf,
+    extensions: Vec<String>,
+    on_missing: MissingComponent,
+}
+
+impl PagesDirectory {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            extensions: ["vue", "jsx", "tsx", "svelte"]
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+            on_missing: MissingComponent::Warn,
+        }
+    }
+
+    /// Replaces the default `vue`, `jsx`, `tsx` and `svelte` extensions.
+    pub fn extensions(mut self, extensions: &[&str]) -> Self {
+        self.extensions = extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.').to_string())
+            .collect();
+        self
+    }
+
+    pub fn on_missing(mut self, on_missing: MissingComponent) -> Self {
+        self.on_missing = on_missing;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn missing_component(&self) -> MissingComponent {
+        self.on_missing
+    }
+
+    /// The file `component` resolves to, if it exists.
+    pub fn resolve(&self, component: &str) -> Option<PathBuf> {
+        if component.is_empty() || component.split('/').any(|part| part == "..") {
+            return None;
+        }
+
+        self.extensions
+            .iter()
+            .map(|ext| self.dir.join(format!("{component}.{ext}")))
+            .find(|path| path.is_file())
+    }
+
+    pub fn exists(&self, component: &str) -> bool {
+        self.resolve(component).is_some()
+    }
+}
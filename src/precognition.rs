@@ -0,0 +1,77 @@
+use crate::validation::ValidationErrors;
+use actix_web::{http, HttpRequest, HttpResponse};
+
+pub static PRECOGNITION: &str = "Precognition";
+pub static PRECOGNITION_SUCCESS: &str = "Precognition-Success";
+pub static PRECOGNITION_VALIDATE_ONLY: &str = "Precognition-Validate-Only";
+
+/// Whether `req` is a Precognition request asking to validate a form without submitting it.
+pub fn is_precognitive(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(PRECOGNITION)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+/// The fields listed in `Precognition-Validate-Only`, or `None` to validate the whole form.
+pub fn validate_only(req: &HttpRequest) -> Option<Vec<String>> {
+    req.headers()
+        .get(PRECOGNITION_VALIDATE_ONLY)
+        .and_then(|v| v.to_str().ok())
+        .map(|fields| {
+            fields
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+}
+
+/// Answers a Precognition request: `204` when the validated fields pass, otherwise `422`
+/// with the errors in the `{message, errors: {field: [message]}}` shape the client expects.
+pub fn precognition_response(
+    req: &HttpRequest,
+    result: Result<(), ValidationErrors>,
+) -> HttpResponse {
+    let mut errors = result.err().unwrap_or_default();
+    if let Some(fields) = validate_only(req) {
+        errors.retain(|field, _| fields.iter().any(|only| only == field));
+    }
+
+    if errors.is_empty() {
+        return HttpResponse::NoContent()
+            .append_header((PRECOGNITION, "true"))
+            .append_header((PRECOGNITION_SUCCESS, "true"))
+            .append_header((http::header::VARY, PRECOGNITION))
+            .finish();
+    }
+    unprocessable(&errors)
+}
+
+/// Answers a Precognition request whose body could not be deserialized with `422` and all of
+/// `errors`, whatever `Precognition-Validate-Only` asks for: validation never ran, so no field
+/// can be reported as passing.
+pub fn precognition_parse_failure(errors: &ValidationErrors) -> HttpResponse {
+    unprocessable(errors)
+}
+
+fn unprocessable(errors: &ValidationErrors) -> HttpResponse {
+    let message = errors
+        .iter()
+        .next()
+        .map(|(_, message)| message.to_string())
+        .unwrap_or_default();
+    let fields: serde_json::Map<String, serde_json::Value> = errors
+        .iter()
+        .map(|(field, message)| (field.to_string(), serde_json::json!([message])))
+        .collect();
+
+    HttpResponse::UnprocessableEntity()
+        .append_header((PRECOGNITION, "true"))
+        .append_header((http::header::VARY, PRECOGNITION))
+        .json(serde_json::json!({
+            "message": message,
+            "errors": fields,
+        }))
+}
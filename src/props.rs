@@ -0,0 +1,110 @@
+#[cfg(feature = "actix")]
+use crate::Inertia;
+#[cfg(feature = "actix")]
+use actix_web::HttpRequest;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// A prop computed only when it is sent.
+pub(crate) type PropFn = Box<dyn FnOnce() -> Result<Value, serde_json::Error> + Send>;
+
+pub(crate) fn prop_fn<F, V>(resolve: F) -> PropFn
+where
+    F: FnOnce() -> V + Send + 'static,
+    V: Serialize,
+{
+    Box::new(move || serde_json::to_value(resolve()))
+}
+
+/// How individual props take part in partial reloads, deferred loading and merging.
+///
+/// These options only decide which keys are sent to the client: a lazy or deferred prop
+/// that is part of the props value, including an `#[inertia(lazy)]` field, is still computed
+/// and serialized on every request. Use [`Inertia::lazy_with`](crate::Inertia::lazy_with) or
+/// [`Inertia::defer_with`](crate::Inertia::defer_with) for props that are expensive to compute.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PropOptions {
+    lazy: Vec<String>,
+    always: Vec<String>,
+    deferred: BTreeMap<String, Vec<String>>,
+    merge: Vec<String>,
+}
+
+impl PropOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only send `key` when a partial reload asks for it.
+    pub fn lazy(mut self, key: &str) -> Self {
+        self.lazy.push(key.to_string());
+        self
+    }
+
+    /// Send `key` on every response, including partial reloads that did not ask for it.
+    pub fn always(mut self, key: &str) -> Self {
+        self.always.push(key.to_string());
+        self
+    }
+
+    /// Leave `key` out of the first visit and let the client fetch it with the rest of `group`.
+    pub fn defer(mut self, key: &str, group: &str) -> Self {
+        self.deferred
+            .entry(group.to_string())
+            .or_default()
+            .push(key.to_string());
+        self
+    }
+
+    /// Ask the client to merge `key` into its current value instead of replacing it.
+    pub fn merge(mut self, key: &str) -> Self {
+        self.merge.push(key.to_string());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lazy.is_empty()
+            && self.always.is_empty()
+            && self.deferred.is_empty()
+            && self.merge.is_empty()
+    }
+
+    pub(crate) fn is_lazy(&self, key: &str) -> bool {
+        self.lazy.iter().any(|k| k == key)
+    }
+
+    pub(crate) fn is_deferred(&self, key: &str) -> bool {
+        self.deferred.values().flatten().any(|k| k == key)
+    }
+
+    pub(crate) fn always_keys(&self) -> impl Iterator<Item = &str> {
+        self.always.iter().map(String::as_str)
+    }
+
+    pub(crate) fn deferred_groups(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.deferred
+    }
+
+    pub(crate) fn merge_keys(&self) -> impl Iterator<Item = &str> {
+        self.merge.iter().map(String::as_str)
+    }
+}
+
+/// A props type that knows which component renders it.
+///
+/// Usually derived with `#[derive(InertiaPage)]` from the `macros` feature, which also makes
+/// the props directly returnable from a handler.
+pub trait InertiaPage: Serialize + Sized {
+    const COMPONENT: &'static str;
+
+    fn prop_options() -> PropOptions {
+        PropOptions::default()
+    }
+
+    #[cfg(feature = "actix")]
+    fn into_inertia(self, req: &HttpRequest) -> Inertia<Self> {
+        Inertia::new(Self::COMPONENT.to_string(), self, req.uri().to_string())
+            .prop_options(Self::prop_options())
+    }
+}
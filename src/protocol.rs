@@ -0,0 +1,674 @@
+//! The Inertia protocol without a web framework: reading the Inertia request headers,
+//! checking asset versions, filtering props for partial reloads and building the page object.
+//!
+//! Framework adapters turn their request into an [`InertiaRequest`] and the [`Rendered`]
+//! result of [`Inertia::render_page`] into their response type.
+//!
+//! ```ignore
+//! let (parts, _body) = request.into_parts();
+//! let request = InertiaRequest::from_parts(&parts);
+//!
+//! match Inertia::new("Dashboard".to_string(), props, request.url.clone())
+//!     .render_page(&request, Some(&factory))?
+//! {
+//!     Rendered::Json(page) => { /* 200, `X-Inertia: true`, JSON body */ }
+//!     Rendered::Html(html) => { /* 200, HTML body */ }
+//!     Rendered::NoRootView(page) => { /* 200, JSON body */ }
+//!     Rendered::NotModified(etag) => { /* 304 with the `ETag`, no body */ }
+//!     Rendered::Conflict(location) => { /* 409 with `X-Inertia-Location` */ }
+//! }
+//! ```
+//!
+//! Send [`Rendered::headers`] with every variant.
+
+use crate::error::InertiaError;
+use crate::factory::ResponseFactory;
+use crate::head::Head;
+use crate::props::{prop_fn, PropOptions};
+use crate::root_view::RootView;
+use crate::size;
+use crate::{
+    Inertia, PURPOSE, SEC_PURPOSE, X_INERTIA, X_INERTIA_DEBUG_FILTERED_PROPS,
+    X_INERTIA_DEBUG_PROP_SIZES, X_INERTIA_ERROR_BAG, X_INERTIA_LOCATION,
+    X_INERTIA_PARTIAL_COMPONENT, X_INERTIA_PARTIAL_EXCEPT, X_INERTIA_PARTIAL_ONLY, X_INERTIA_RESET,
+    X_INERTIA_VERSION,
+};
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Cookie that carries validation errors across the redirect back to a form.
+pub const ERRORS_COOKIE: &str = "inertia_errors";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InertiaResponse<T> {
+    component: String,
+    props: T,
+    url: String,
+    version: Option<String>,
+    #[serde(rename = "encryptHistory", default)]
+    encrypt_history: bool,
+    #[serde(rename = "clearHistory", default)]
+    clear_history: bool,
+    #[serde(
+        rename = "deferredProps",
+        skip_serializing_if = "BTreeMap::is_empty",
+        default
+    )]
+    deferred_props: BTreeMap<String, Vec<String>>,
+    #[serde(rename = "mergeProps", skip_serializing_if = "Vec::is_empty", default)]
+    merge_props: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct HtmlResponseContext {
+    data_page: String,
+    head: String,
+}
+
+/// What the protocol needs to know about a request.
+///
+/// Built from the `http` request parts with [`InertiaRequest::from_parts`]; adapters then
+/// fill in what their framework keeps elsewhere, such as scope settings and shared props.
+#[derive(Clone, Debug, Default)]
+pub struct InertiaRequest {
+    pub method: http::Method,
+    /// The path and query of the request.
+    pub url: String,
+    /// Whether the request carries `X-Inertia`, i.e. is a visit made by the Inertia client.
+    pub inertia: bool,
+    pub version: Option<String>,
+    pub partial_component: Option<String>,
+    pub partial_only: Option<Vec<String>>,
+    pub partial_except: Option<Vec<String>>,
+    pub reset: Vec<String>,
+    pub error_bag: Option<String>,
+    /// Whether the client is prefetching the page, with `Purpose: prefetch`, and may never show
+    /// it. Handlers should skip side effects such as view counters.
+    pub prefetch: bool,
+    /// The `If-None-Match` header of a client that already has a version of the page.
+    pub if_none_match: Option<String>,
+    /// Validation errors flashed by the previous request.
+    pub errors: Option<Value>,
+    /// Props added for this request only. Page props with the same key win.
+    pub shared_props: Map<String, Value>,
+    /// Encrypt the history unless the page says otherwise.
+    pub encrypt_history: bool,
+    /// The root view to render with unless the page picks one.
+    pub root_view: Option<String>,
+    /// Render without checking the asset version, because the adapter's check is turned off.
+    pub skip_version_check: bool,
+}
+
+impl InertiaRequest {
+    pub fn from_parts(parts: &http::request::Parts) -> Self {
+        let url = parts.uri.path_and_query().map_or_else(
+            || parts.uri.path().to_string(),
+            |pq| pq.as_str().to_string(),
+        );
+        Self::from_headers(parts.method.clone(), url, |name| {
+            parts.headers.get(name).and_then(|v| v.to_str().ok())
+        })
+    }
+
+    /// Reads the Inertia headers through `header`, for frameworks that use other `http` types.
+    pub fn from_headers<'a>(
+        method: http::Method,
+        url: String,
+        header: impl Fn(&str) -> Option<&'a str>,
+    ) -> Self {
+        let list = |name| header(name).map(|v: &str| header_list(v).map(str::to_string).collect());
+
+        Self {
+            method,
+            url,
+            inertia: header(X_INERTIA).is_some(),
+            version: header(X_INERTIA_VERSION).map(str::to_string),
+            partial_component: header(X_INERTIA_PARTIAL_COMPONENT).map(str::to_string),
+            partial_only: list(X_INERTIA_PARTIAL_ONLY),
+            partial_except: list(X_INERTIA_PARTIAL_EXCEPT),
+            reset: list(X_INERTIA_RESET).unwrap_or_default(),
+            error_bag: header(X_INERTIA_ERROR_BAG)
+                .filter(|bag| !bag.is_empty())
+                .map(str::to_string),
+            prefetch: [PURPOSE, SEC_PURPOSE]
+                .into_iter()
+                .filter_map(&header)
+                .any(is_prefetch_purpose),
+            if_none_match: header("if-none-match").map(str::to_string),
+            errors: header("cookie").and_then(flashed_errors),
+            shared_props: Map::new(),
+            encrypt_history: false,
+            root_view: None,
+            skip_version_check: false,
+        }
+    }
+
+    /// Whether this is an Inertia visit whose asset version is not accepted, which the
+    /// client answers with a full page load.
+    pub fn version_conflict(&self, accepts: impl Fn(Option<&str>) -> bool) -> bool {
+        self.method == http::Method::GET && self.inertia && !accepts(self.version.as_deref())
+    }
+}
+
+/// Whether a `Purpose` or `Sec-Purpose` header value asks for a prefetch.
+pub(crate) fn is_prefetch_purpose(value: &str) -> bool {
+    header_list(value)
+        .filter_map(|purpose| purpose.split(';').next())
+        .any(|purpose| purpose.trim().eq_ignore_ascii_case("prefetch"))
+}
+
+/// The flashed errors in a `Cookie` header.
+pub(crate) fn flashed_errors(cookies: &str) -> Option<Value> {
+    cookies
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == ERRORS_COOKIE)
+        .and_then(|(_, value)| {
+            percent_decode_str(value.trim_matches('"'))
+                .decode_utf8()
+                .ok()
+        })
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .filter(Value::is_object)
+}
+
+/// The value of the errors cookie: percent-encoded JSON, as messages may contain `;`, `,`,
+/// `%` or non-ASCII text that a cookie value cannot carry.
+#[cfg(feature = "actix")]
+pub(crate) fn encode_flashed_errors(errors: &Value) -> String {
+    percent_encoding::utf8_percent_encode(&errors.to_string(), percent_encoding::NON_ALPHANUMERIC)
+        .to_string()
+}
+
+/// The response to send for a page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rendered {
+    /// An Inertia visit: `200` with the page object as JSON and `X-Inertia: true`.
+    Json(String),
+    /// A full page load: `200` with the root view.
+    Html(String),
+    /// A full page load without a root view file: `200` with the page object as JSON.
+    NoRootView(String),
+    /// A stale asset version: `409 Conflict` with `X-Inertia-Location` set to this url.
+    Conflict(String),
+    /// An Inertia visit for a page object the client already has: `304 Not Modified` with
+    /// this `ETag`.
+    NotModified(String),
+}
+
+/// A strong `ETag` for a serialized page object.
+///
+/// The page object holds everything a response depends on, from shared props and the asset
+/// version to the props left after a partial reload, so any change gives a new tag. The hash
+/// is stable across builds and processes, so every instance behind a load balancer agrees.
+pub fn etag(page: &str) -> String {
+    format!("\"{}\"", crate::hash::stable_hash(page.as_bytes()))
+}
+
+/// Whether `If-None-Match` lists `etag`, using the weak comparison conditional `GET`s use.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.trim() == "*"
+        || header_list(if_none_match).any(|tag| tag.trim_start_matches("W/") == etag)
+}
+
+impl Rendered {
+    /// The headers to send with this response besides `Content-Type`.
+    pub fn headers(
+        &self,
+        request: &InertiaRequest,
+        factory: Option<&ResponseFactory>,
+    ) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::new();
+        match self {
+            Rendered::Json(page) => {
+                headers.push((X_INERTIA, "true".to_string()));
+                if factory.is_none_or(ResponseFactory::etags) {
+                    headers.push(("ETag", etag(page)));
+                }
+            }
+            Rendered::NotModified(etag) => {
+                headers.push((X_INERTIA, "true".to_string()));
+                headers.push(("ETag", etag.clone()));
+            }
+            Rendered::Conflict(location) => headers.push((X_INERTIA_LOCATION, location.clone())),
+            Rendered::Html(_) | Rendered::NoRootView(_) => {}
+        }
+
+        let cache = factory.and_then(|factory| match self {
+            Rendered::Conflict(_) => None,
+            _ if request.prefetch => factory.prefetch_cache(),
+            Rendered::Json(_) | Rendered::NotModified(_) => factory.json_cache(),
+            Rendered::Html(_) | Rendered::NoRootView(_) => factory.html_cache(),
+        });
+
+        // The same url answers with HTML or JSON, and partial reloads change the props. A
+        // cacheable prefetch must not be reused for a real visit, which skips its side effects.
+        let mut vary = match self {
+            Rendered::Conflict(_) => X_INERTIA.to_string(),
+            _ => VARY_PAGE.join(", "),
+        };
+        if request.prefetch && cache.is_some() {
+            vary = format!("{vary}, {PURPOSE}, {SEC_PURPOSE}");
+        }
+        headers.push(("Vary", vary));
+
+        if let Some(cache) = cache {
+            headers.push(("Cache-Control", cache.to_string()));
+        }
+        headers
+    }
+
+    /// Whether the flashed validation errors were shown and the cookie can be cleared. A
+    /// prefetched page may never be shown, and a conflict is followed by a full page load.
+    pub fn forgets_errors(&self, request: &InertiaRequest) -> bool {
+        request.errors.is_some() && !request.prefetch && !matches!(self, Rendered::Conflict(_))
+    }
+}
+
+/// What a response left out or why it is too big, for debugging.
+///
+/// Filtered props are only reported in the factory's diagnostic mode, see
+/// [`ResponseFactory::set_diagnostics`], prop sizes only for pages over the factory's
+/// [`SizeLimit`](crate::SizeLimit) in dev mode.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// Props that partial reload filtering, lazy or deferred props left out of the page.
+    pub filtered_props: Option<Vec<String>>,
+    /// The biggest top-level props and their serialized sizes in bytes, biggest first.
+    pub prop_sizes: Option<Vec<(String, usize)>>,
+}
+
+impl Diagnostics {
+    /// With filtered props, the partial reload headers the client sent, echoed back, and the
+    /// filtered props in `X-Inertia-Debug-Filtered-Props`. With prop sizes,
+    /// `X-Inertia-Debug-Prop-Sizes`.
+    pub fn headers(&self, request: &InertiaRequest) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::new();
+        if let Some(filtered_props) = &self.filtered_props {
+            let echoed = [
+                (X_INERTIA_ERROR_BAG, request.error_bag.clone()),
+                (
+                    X_INERTIA_PARTIAL_COMPONENT,
+                    request.partial_component.clone(),
+                ),
+                (
+                    X_INERTIA_PARTIAL_ONLY,
+                    request.partial_only.as_ref().map(|only| only.join(",")),
+                ),
+                (
+                    X_INERTIA_PARTIAL_EXCEPT,
+                    request
+                        .partial_except
+                        .as_ref()
+                        .map(|except| except.join(",")),
+                ),
+            ];
+            headers.extend(
+                echoed
+                    .into_iter()
+                    .filter_map(|(name, value)| Some((name, value?))),
+            );
+            headers.push((X_INERTIA_DEBUG_FILTERED_PROPS, filtered_props.join(",")));
+        }
+        if let Some(prop_sizes) = &self.prop_sizes {
+            headers.push((X_INERTIA_DEBUG_PROP_SIZES, size::format_sizes(prop_sizes)));
+        }
+        headers
+    }
+
+    fn new(
+        filtered_props: Option<Vec<String>>,
+        prop_sizes: Option<Vec<(String, usize)>>,
+    ) -> Option<Self> {
+        (filtered_props.is_some() || prop_sizes.is_some()).then_some(Self {
+            filtered_props,
+            prop_sizes,
+        })
+    }
+}
+
+/// The request headers a page response depends on.
+const VARY_PAGE: [&str; 5] = [
+    X_INERTIA,
+    X_INERTIA_PARTIAL_COMPONENT,
+    X_INERTIA_PARTIAL_ONLY,
+    X_INERTIA_PARTIAL_EXCEPT,
+    X_INERTIA_RESET,
+];
+
+pub(crate) fn header_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(|s| s.trim()).filter(|s| !s.is_empty())
+}
+
+impl<T: Serialize> Inertia<T> {
+    pub fn new(component: String, props: T, url: String) -> Self {
+        Self {
+            component,
+            props,
+            url: Some(url),
+            encrypt_history: None,
+            clear_history: None,
+            options: PropOptions::default(),
+            root_view: None,
+            head: None,
+            error: None,
+            resolvers: Vec::new(),
+        }
+    }
+
+    /// Replaces the per-prop options, e.g. with the ones derived for an [`InertiaPage`](crate::InertiaPage).
+    pub fn prop_options(mut self, options: PropOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Only send `key` when a partial reload asks for it.
+    pub fn lazy(mut self, key: &str) -> Self {
+        self.options = self.options.lazy(key);
+        self
+    }
+
+    /// Like [`lazy`](Inertia::lazy), with a value computed by `resolve` only when a partial
+    /// reload asks for `key`.
+    pub fn lazy_with<F, V>(mut self, key: &str, resolve: F) -> Self
+    where
+        F: FnOnce() -> V + Send + 'static,
+        V: Serialize,
+    {
+        self.options = self.options.lazy(key);
+        self.resolvers.push((key.to_string(), prop_fn(resolve)));
+        self
+    }
+
+    /// Send `key` on partial reloads even when it was not asked for.
+    pub fn always(mut self, key: &str) -> Self {
+        self.options = self.options.always(key);
+        self
+    }
+
+    /// Load `key` after the first render, together with the other props in `group`.
+    pub fn defer(mut self, key: &str, group: &str) -> Self {
+        self.options = self.options.defer(key, group);
+        self
+    }
+
+    /// Like [`defer`](Inertia::defer), with a value computed by `resolve` only when the client
+    /// loads `group`.
+    pub fn defer_with<F, V>(mut self, key: &str, group: &str, resolve: F) -> Self
+    where
+        F: FnOnce() -> V + Send + 'static,
+        V: Serialize,
+    {
+        self.options = self.options.defer(key, group);
+        self.resolvers.push((key.to_string(), prop_fn(resolve)));
+        self
+    }
+
+    /// Merge `key` into the client's current value instead of replacing it.
+    pub fn merge(mut self, key: &str) -> Self {
+        self.options = self.options.merge(key);
+        self
+    }
+
+    /// Override whether the client encrypts the history state for this page.
+    pub fn encrypt_history(mut self, encrypt: bool) -> Self {
+        self.encrypt_history = Some(encrypt);
+        self
+    }
+
+    /// Ask the client to clear its encrypted history, e.g. after logging out.
+    pub fn clear_history(mut self) -> Self {
+        self.clear_history = Some(true);
+        self
+    }
+
+    /// Tags for the document head on full page loads, see [`Head`].
+    pub fn head(mut self, head: Head) -> Self {
+        self.head = Some(head);
+        self
+    }
+
+    /// Render this page with a root view registered with
+    /// [`ResponseFactory::add_root_view`].
+    pub fn root_view(mut self, name: &str) -> Self {
+        self.root_view = Some(name.to_string());
+        self
+    }
+
+    /// Serializes the props now, so the page can be stored until the request is known.
+    #[cfg(feature = "axum")]
+    pub(crate) fn into_value(self) -> Inertia<Value> {
+        let (props, error) = match serde_json::to_value(self.props) {
+            Ok(props) => (props, self.error),
+            Err(err) => (Value::Null, self.error.or(Some(err.into()))),
+        };
+
+        Inertia {
+            component: self.component,
+            props,
+            url: self.url,
+            encrypt_history: self.encrypt_history,
+            clear_history: self.clear_history,
+            options: self.options,
+            root_view: self.root_view,
+            head: self.head,
+            error,
+            resolvers: self.resolvers,
+        }
+    }
+
+    /// Builds the response for `request` without touching any framework types.
+    pub fn render_page(
+        self,
+        request: &InertiaRequest,
+        factory: Option<&ResponseFactory>,
+    ) -> Result<Rendered, InertiaError> {
+        self.render_page_with_diagnostics(request, factory)
+            .map(|(rendered, _)| rendered)
+    }
+
+    /// Like [`render_page`](Inertia::render_page), and also returns [`Diagnostics`] for
+    /// Inertia visits when the factory's diagnostic mode is on, and for pages over its size
+    /// limit in dev mode.
+    pub fn render_page_with_diagnostics(
+        self,
+        request: &InertiaRequest,
+        factory: Option<&ResponseFactory>,
+    ) -> Result<(Rendered, Option<Diagnostics>), InertiaError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        trace_span!(
+            "inertia.render",
+            component = %self.component,
+            inertia = request.inertia,
+            payload_size = tracing::field::Empty,
+        );
+        if let Some(factory) = factory {
+            factory.check_component(&self.component)?;
+        }
+        let root_view_name = self.root_view.as_deref().or(request.root_view.as_deref());
+        let root_view = factory
+            .map(|factory| factory.select_root_view(root_view_name))
+            .transpose()?;
+
+        let version = root_view
+            .and_then(RootView::get_version)
+            .or_else(|| factory.map(|factory| factory.get_version()))
+            .filter(|v| !v.is_empty());
+        if let Some(version) = version.as_ref().filter(|_| !request.skip_version_check) {
+            // The middlewares accept the version of any root view, but only the one this page
+            // renders with is current for it. A view picked with `.root_view(name)` is only
+            // known here.
+            if request.version_conflict(|v| v == Some(version.as_str())) {
+                if let Some(factory) = factory {
+                    factory.record_version_conflict(&request.url, request.version.as_deref());
+                }
+                return Ok((Rendered::Conflict(request.url.clone()), None));
+            }
+        }
+
+        let encrypt_history = self.encrypt_history.unwrap_or_else(|| {
+            request.encrypt_history || factory.is_some_and(|factory| factory.encrypt_history())
+        });
+        let clear_history = self
+            .clear_history
+            .unwrap_or_else(|| factory.is_some_and(|factory| factory.clear_history()));
+
+        let mut props = {
+            trace_span!("inertia.serialize_props");
+            serde_json::to_value(self.props)?
+        };
+        let mut deferred_props = BTreeMap::new();
+        let mut merge_props = Vec::new();
+        let mut filtered_props = Vec::new();
+
+        if let Value::Object(ref mut map) = props {
+            for (key, value) in &request.shared_props {
+                map.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+            for (key, _) in &self.resolvers {
+                map.entry(key.clone()).or_insert(Value::Null);
+            }
+            let keys: Vec<String> = map.keys().cloned().collect();
+            {
+                trace_span!(
+                    "inertia.filter_props",
+                    props = keys.len(),
+                    filtered = tracing::field::Empty,
+                );
+                deferred_props = filter_props(map, &self.component, &self.options, request);
+                filtered_props = keys
+                    .into_iter()
+                    .filter(|key| !map.contains_key(key))
+                    .collect();
+                trace_record!("filtered", filtered_props.len());
+            }
+            for (key, resolve) in self.resolvers {
+                if let Some(value) = map.get_mut(&key) {
+                    *value = resolve()?;
+                }
+            }
+
+            #[cfg(feature = "tracing")]
+            if factory.is_some_and(ResponseFactory::log_props) {
+                tracing::debug!(
+                    target: "actix_inertia",
+                    component = %self.component,
+                    props = %serde_json::Value::Object(map.clone()),
+                    "page props"
+                );
+            }
+            merge_props = self
+                .options
+                .merge_keys()
+                .filter(|key| map.contains_key(*key) && !request.reset.iter().any(|k| k == key))
+                .map(str::to_string)
+                .collect();
+
+            if let Some(errors) = &request.errors {
+                let key = factory.map_or("errors", |factory| factory.errors_key());
+                map.entry(key).or_insert_with(|| errors.clone());
+            }
+        }
+
+        let page = InertiaResponse {
+            component: self.component,
+            props,
+            url: self.url.unwrap_or_else(|| request.url.clone()),
+            version,
+            encrypt_history,
+            clear_history,
+            deferred_props,
+            merge_props,
+        };
+
+        if request.inertia {
+            let filtered_props = factory
+                .is_some_and(ResponseFactory::diagnostics)
+                .then_some(filtered_props);
+            let json = serde_json::to_string(&page)?;
+            trace_record!("payload_size", json.len());
+            let prop_sizes = match factory {
+                Some(factory) => factory.check_size(&page.component, json.len(), &page.props)?,
+                None => None,
+            };
+            let diagnostics = Diagnostics::new(filtered_props, prop_sizes);
+            let page = json;
+            if let Some(if_none_match) = &request.if_none_match {
+                let etag = etag(&page);
+                if matches!(request.method, http::Method::GET | http::Method::HEAD)
+                    && factory.is_none_or(ResponseFactory::etags)
+                    && etag_matches(if_none_match, &etag)
+                {
+                    return Ok((Rendered::NotModified(etag), diagnostics));
+                }
+            }
+            return Ok((Rendered::Json(page), diagnostics));
+        }
+
+        let ctx = HtmlResponseContext {
+            data_page: serde_json::to_string(&page)?,
+            head: self.head.map(|head| head.render()).unwrap_or_default(),
+        };
+        trace_record!("payload_size", ctx.data_page.len());
+        let prop_sizes = match factory {
+            Some(factory) => {
+                factory.check_size(&page.component, ctx.data_page.len(), &page.props)?
+            }
+            None => None,
+        };
+        let html = match root_view {
+            Some(root_view) => {
+                trace_span!("inertia.root_view", path = root_view.path());
+                root_view.render(&ctx.data_page, &ctx.head)?
+            }
+            None => None,
+        };
+
+        let rendered = match html {
+            Some(html) => Rendered::Html(html),
+            None => Rendered::NoRootView(ctx.data_page),
+        };
+        Ok((rendered, Diagnostics::new(None, prop_sizes)))
+    }
+}
+
+/// Applies a partial reload to `props`, or leaves out lazy and deferred props on other
+/// visits. Returns the deferred groups the client should load afterwards.
+fn filter_props(
+    props: &mut Map<String, Value>,
+    component: &str,
+    options: &PropOptions,
+    request: &InertiaRequest,
+) -> BTreeMap<String, Vec<String>> {
+    let partial = match &request.partial_component {
+        Some(partial_component) => partial_component == component,
+        None => request.partial_only.is_some() || request.partial_except.is_some(),
+    };
+
+    if !partial {
+        props.retain(|k, _| !options.is_lazy(k) && !options.is_deferred(k));
+        return options.deferred_groups().clone();
+    }
+
+    let always: Vec<(String, Value)> = options
+        .always_keys()
+        .filter_map(|key| props.get(key).map(|v| (key.to_string(), v.clone())))
+        .collect();
+
+    if let Some(only) = &request.partial_only {
+        props.retain(|k, _| only.contains(k));
+    }
+    if let Some(except) = &request.partial_except {
+        for key in except {
+            props.remove(key);
+        }
+    }
+
+    props.extend(always);
+    BTreeMap::new()
+}
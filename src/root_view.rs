@@ -0,0 +1,231 @@
+//! HTML shells for full page loads. Besides the default root view of the
+//! [`ResponseFactory`](crate::ResponseFactory), named root views let one server host several
+//! frontends, e.g. a customer app and an admin app with their own bundles.
+//!
+//! ```ignore
+//! factory.add_root_view(
+//!     "admin",
+//!     RootView::new("templates/admin.html")
+//!         .version("admin-1")
+//!         .vite_entry("/resources/js/admin.ts"),
+//! );
+//!
+//! App::new().service(
+//!     web::scope("/admin")
+//!         .wrap(RootViewMiddleware::new("admin"))
+//!         .route("", web::get().to(dashboard)),
+//! )
+//! ```
+
+#[cfg(feature = "actix")]
+use crate::actix::{conflict, inertia_request};
+use crate::error::InertiaError;
+#[cfg(feature = "actix")]
+use crate::factory::ResponseFactory;
+use crate::factory::VersionFn;
+#[cfg(feature = "actix")]
+use actix_service::{forward_ready, Service, Transform};
+#[cfg(feature = "actix")]
+use actix_web::body::EitherBody;
+#[cfg(feature = "actix")]
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+#[cfg(feature = "actix")]
+use actix_web::{Error, HttpMessage, Result};
+#[cfg(feature = "actix")]
+use futures::future::{ok, Ready};
+#[cfg(feature = "actix")]
+use futures_util::future::LocalBoxFuture;
+use std::fs;
+use std::sync::Arc;
+
+/// An HTML template with a `{{DATA_PAGE}}` placeholder.
+///
+/// A `{{VITE}}` placeholder is replaced with a module script tag for the view's Vite entry,
+/// and `{{HEAD}}` with the page's [`Head`](crate::Head) tags.
+#[derive(Clone)]
+pub struct RootView {
+    path: String,
+    version: Option<VersionFn>,
+    vite_entry: Option<String>,
+}
+
+impl RootView {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            version: None,
+            vite_entry: None,
+        }
+    }
+
+    /// Gives pages rendered with this view their own asset version instead of the factory's.
+    pub fn version(self, version: impl Into<String>) -> Self {
+        let version = version.into();
+        self.version_with(move || version.clone())
+    }
+
+    pub fn version_with<F>(mut self, version: F) -> Self
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        self.version = Some(Arc::new(version));
+        self
+    }
+
+    /// The script loaded by the `{{VITE}}` placeholder, e.g. `/resources/js/admin.ts`.
+    pub fn vite_entry(mut self, entry: impl Into<String>) -> Self {
+        self.vite_entry = Some(entry.into());
+        self
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub(crate) fn set_path(&mut self, path: &str) {
+        self.path = path.to_string();
+    }
+
+    /// The view's own asset version, if it has one.
+    pub fn get_version(&self) -> Option<String> {
+        self.version.as_ref().map(|version| version())
+    }
+
+    /// Renders the template with the page object and head tags, or `Ok(None)` when the
+    /// template file does not exist.
+    pub fn render(&self, data_page: &str, head: &str) -> Result<Option<String>, InertiaError> {
+        let html = match fs::read_to_string(&self.path) {
+            Ok(html) => html,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(InertiaError::Template(format!("{}: {err}", self.path))),
+        };
+        if !html.contains("{{DATA_PAGE}}") {
+            return Err(InertiaError::Template(format!(
+                "{} has no {{{{DATA_PAGE}}}} placeholder",
+                self.path
+            )));
+        }
+
+        let vite = match &self.vite_entry {
+            Some(entry) => format!(
+                "<script type=\"module\" src=\"{}\"></script>",
+                entry.replace('"', "&quot;")
+            ),
+            None => String::new(),
+        };
+        let escaped = data_page.replace("\"", "&quot;");
+        Ok(Some(substitute(
+            &html,
+            &[
+                ("{{VITE}}", &vite),
+                ("{{HEAD}}", head),
+                ("{{DATA_PAGE}}", &escaped),
+            ],
+        )))
+    }
+}
+
+/// Replaces the placeholders in one pass over `template`. Inserted text is never scanned
+/// again, so a user-supplied title containing `{{DATA_PAGE}}` stays text.
+fn substitute(template: &str, values: &[(&str, &str)]) -> String {
+    let mut html = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        html.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match values.iter().find(|(name, _)| rest.starts_with(name)) {
+            Some((name, value)) => {
+                html.push_str(value);
+                rest = &rest[name.len()..];
+            }
+            None => {
+                html.push_str("{{");
+                rest = &rest[2..];
+            }
+        }
+    }
+    html.push_str(rest);
+    html
+}
+
+/// Request marker set by [`RootViewMiddleware`].
+#[cfg(feature = "actix")]
+#[derive(Clone)]
+pub(crate) struct SelectedRootView(pub(crate) String);
+
+/// Renders every Inertia page inside the wrapped scope with a named root view.
+///
+/// When the root view has its own version, Inertia visits with a different
+/// `X-Inertia-Version` are answered with `409 Conflict` before the handler runs.
+#[cfg(feature = "actix")]
+pub struct RootViewMiddleware {
+    name: String,
+}
+
+#[cfg(feature = "actix")]
+impl RootViewMiddleware {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+#[cfg(feature = "actix")]
+impl<S, B> Transform<S, ServiceRequest> for RootViewMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RootViewMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RootViewMiddlewareService {
+            service,
+            name: self.name.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "actix")]
+pub struct RootViewMiddlewareService<S> {
+    service: S,
+    name: String,
+}
+
+#[cfg(feature = "actix")]
+impl<S, B> Service<ServiceRequest> for RootViewMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let factory = ResponseFactory::find(req.request());
+        let version = factory
+            .and_then(|factory| factory.get_root_view(&self.name))
+            .and_then(RootView::get_version);
+        if let (Some(factory), Some(version)) = (factory, version) {
+            let request = inertia_request(req.request());
+            if request.version_conflict(|v| v == Some(version.as_str())) {
+                factory.record_version_conflict(&request.url, request.version.as_deref());
+                let res = conflict(&request.url).map_into_right_body();
+                return Box::pin(async move { Ok(req.into_response(res)) });
+            }
+        }
+
+        req.extensions_mut()
+            .insert(SelectedRootView(self.name.clone()));
+        let fut = self.service.call(req);
+        Box::pin(async move { Ok(fut.await?.map_into_left_body()) })
+    }
+}
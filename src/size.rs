@@ -0,0 +1,85 @@
+use serde_json::Value;
+
+/// What to do outside dev mode when a page object is over its [`SizeLimit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Oversized {
+    /// Log a warning and send the page anyway.
+    Warn,
+    /// Answer `500 Internal Server Error` instead of sending the page.
+    Reject,
+}
+
+/// A limit on the serialized size of the page object, to catch props that accidentally ship
+/// whole tables in the `data-page` attribute.
+///
+/// Over the limit, dev mode logs the biggest top-level props and their sizes, and can also
+/// send them in `X-Inertia-Debug-Prop-Sizes`. Outside dev mode, [`Oversized`] decides.
+#[derive(Clone, Debug)]
+pub struct SizeLimit {
+    bytes: usize,
+    on_oversized: Oversized,
+    report_header: bool,
+}
+
+impl SizeLimit {
+    pub fn new(bytes: usize) -> Self {
+        Self {
+            bytes,
+            on_oversized: Oversized::Warn,
+            report_header: false,
+        }
+    }
+
+    pub fn on_oversized(mut self, on_oversized: Oversized) -> Self {
+        self.on_oversized = on_oversized;
+        self
+    }
+
+    /// In dev mode, also list the biggest props in the `X-Inertia-Debug-Prop-Sizes` header.
+    pub fn report_header(mut self, report: bool) -> Self {
+        self.report_header = report;
+        self
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    pub fn oversized(&self) -> Oversized {
+        self.on_oversized
+    }
+
+    pub fn reports_header(&self) -> bool {
+        self.report_header
+    }
+}
+
+/// How many props a size report lists.
+const REPORTED_PROPS: usize = 5;
+
+/// The biggest top-level props and their serialized sizes, biggest first.
+pub(crate) fn prop_sizes(props: &Value) -> Vec<(String, usize)> {
+    let Value::Object(props) = props else {
+        return Vec::new();
+    };
+
+    let mut sizes: Vec<(String, usize)> = props
+        .iter()
+        .map(|(key, value)| {
+            let size = serde_json::to_string(value).map_or(0, |json| json.len());
+            (key.clone(), size)
+        })
+        .collect();
+    sizes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    sizes.truncate(REPORTED_PROPS);
+    sizes
+}
+
+/// `users=52340, posts=1200`
+pub(crate) fn format_sizes(sizes: &[(String, usize)]) -> String {
+    sizes
+        .iter()
+        .map(|(key, size)| format!("{key}={size}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,191 @@
+//! Helpers for testing Inertia handlers with `actix_web::test`.
+//!
+//! ```ignore
+//! let req = test::TestRequest::get()
+//!     .uri("/users")
+//!     .inertia("1.0")
+//!     .to_request();
+//! let resp = test::call_service(&app, req).await;
+//!
+//! assert_inertia(resp)
+//!     .await
+//!     .component("Users/Index")
+//!     .has("users", 10)
+//!     .where_("users.0.name", "Ada")
+//!     .missing("secret");
+//! ```
+
+use crate::pages::PagesDirectory;
+use crate::{
+    X_INERTIA, X_INERTIA_ERROR_BAG, X_INERTIA_PARTIAL_COMPONENT, X_INERTIA_PARTIAL_EXCEPT,
+    X_INERTIA_PARTIAL_ONLY, X_INERTIA_VERSION,
+};
+use actix_web::body::MessageBody;
+use actix_web::dev::ServiceResponse;
+use actix_web::test::{self, TestRequest};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Adds the headers the Inertia client sends to a [`TestRequest`].
+pub trait InertiaTestRequest {
+    /// Marks the request as an Inertia visit made with asset `version`.
+    fn inertia(self, version: &str) -> Self;
+
+    /// A partial reload of `component` returning only the `only` props.
+    fn partial(self, component: &str, only: &[&str]) -> Self;
+
+    /// A partial reload of `component` returning every prop except `except`.
+    fn partial_except(self, component: &str, except: &[&str]) -> Self;
+
+    fn error_bag(self, bag: &str) -> Self;
+}
+
+impl InertiaTestRequest for TestRequest {
+    fn inertia(self, version: &str) -> Self {
+        self.insert_header((X_INERTIA, "true"))
+            .insert_header((X_INERTIA_VERSION, version))
+    }
+
+    fn partial(self, component: &str, only: &[&str]) -> Self {
+        self.insert_header((X_INERTIA_PARTIAL_COMPONENT, component))
+            .insert_header((X_INERTIA_PARTIAL_ONLY, only.join(",")))
+    }
+
+    fn partial_except(self, component: &str, except: &[&str]) -> Self {
+        self.insert_header((X_INERTIA_PARTIAL_COMPONENT, component))
+            .insert_header((X_INERTIA_PARTIAL_EXCEPT, except.join(",")))
+    }
+
+    fn error_bag(self, bag: &str) -> Self {
+        self.insert_header((X_INERTIA_ERROR_BAG, bag))
+    }
+}
+
+/// Reads the page object out of `resp`, from a JSON body or the `data-page` attribute of
+/// an HTML body.
+///
+/// Panics when the response carries no page object.
+pub async fn assert_inertia<B: MessageBody>(resp: ServiceResponse<B>) -> AssertableInertia {
+    let status = resp.status();
+    let body = test::read_body(resp).await;
+    let body = String::from_utf8_lossy(&body);
+
+    let page = serde_json::from_str::<Value>(&body)
+        .ok()
+        .filter(|page| page.get("component").is_some())
+        .or_else(|| data_page(&body))
+        .unwrap_or_else(|| panic!("response ({status}) is not an Inertia page: {body}"));
+
+    AssertableInertia { page }
+}
+
+fn data_page(html: &str) -> Option<Value> {
+    let start = html.find("data-page=\"")? + "data-page=\"".len();
+    let end = start + html[start..].find('"')?;
+    let json = html[start..end]
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    serde_json::from_str(&json).ok()
+}
+
+/// Fluent assertions over an Inertia page object. Every check panics on failure.
+///
+/// Prop paths are dotted, e.g. `user.name` or `users.0.name`.
+pub struct AssertableInertia {
+    page: Value,
+}
+
+impl AssertableInertia {
+    pub fn page(&self) -> &Value {
+        &self.page
+    }
+
+    pub fn props(&self) -> &Value {
+        &self.page["props"]
+    }
+
+    /// The prop at `path`, if present.
+    pub fn prop(&self, path: &str) -> Option<&Value> {
+        path.split('.')
+            .try_fold(self.props(), |value, key| match value {
+                Value::Object(map) => map.get(key),
+                Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            })
+    }
+
+    pub fn component(self, component: &str) -> Self {
+        assert_eq!(
+            self.page["component"], component,
+            "unexpected Inertia component"
+        );
+        self
+    }
+
+    /// Asserts that the page component has a file in `pages`.
+    pub fn component_exists(self, pages: &PagesDirectory) -> Self {
+        let component = self.page["component"].as_str().unwrap_or_default();
+        assert!(
+            pages.exists(component),
+            "Inertia page component `{component}` was not found in {}",
+            pages.dir().display()
+        );
+        self
+    }
+
+    pub fn url(self, url: &str) -> Self {
+        assert_eq!(self.page["url"], url, "unexpected Inertia page url");
+        self
+    }
+
+    pub fn version(self, version: &str) -> Self {
+        assert_eq!(
+            self.page["version"], version,
+            "unexpected Inertia asset version"
+        );
+        self
+    }
+
+    /// Asserts that `path` exists.
+    pub fn exists(self, path: &str) -> Self {
+        assert!(
+            self.prop(path).is_some(),
+            "prop `{path}` is missing from {}",
+            self.props()
+        );
+        self
+    }
+
+    /// Asserts that `path` is an array or object with `count` entries.
+    pub fn has(self, path: &str, count: usize) -> Self {
+        let len = match self.prop(path) {
+            Some(Value::Array(items)) => items.len(),
+            Some(Value::Object(map)) => map.len(),
+            Some(other) => panic!("prop `{path}` is not a collection: {other}"),
+            None => panic!("prop `{path}` is missing from {}", self.props()),
+        };
+        assert_eq!(len, count, "prop `{path}` has the wrong number of entries");
+        self
+    }
+
+    /// Asserts that `path` equals `expected`.
+    pub fn where_<T: Serialize>(self, path: &str, expected: T) -> Self {
+        let expected = serde_json::to_value(expected).expect("expected value serializes");
+        match self.prop(path) {
+            Some(actual) => assert_eq!(actual, &expected, "prop `{path}` has the wrong value"),
+            None => panic!("prop `{path}` is missing from {}", self.props()),
+        }
+        self
+    }
+
+    /// Asserts that `path` is absent.
+    pub fn missing(self, path: &str) -> Self {
+        if let Some(value) = self.prop(path) {
+            panic!("prop `{path}` should be missing but is {value}");
+        }
+        self
+    }
+}
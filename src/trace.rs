@@ -0,0 +1,69 @@
+//! Spans and events for rendering, compiled to nothing without the `tracing` feature.
+//!
+//! Span names start with `inertia.`, events use the `actix_inertia` target.
+
+/// Enters a span until the end of the enclosing block.
+macro_rules! trace_span {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!($name $(, $($fields)*)?).entered();
+    };
+}
+
+/// Records a field declared as `tracing::field::Empty` on the current span.
+macro_rules! trace_record {
+    ($field:literal, $value:expr) => {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record($field, $value);
+    };
+}
+
+/// Emits an event, e.g. `trace_event!(warn, url, "asset version conflict")`.
+macro_rules! trace_event {
+    ($level:ident, $($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!(target: "actix_inertia", $($args)*);
+    };
+}
+
+/// Reports a problem the application should see: a `tracing` event with the `tracing`
+/// feature, a `log` record without it. Either is silent until the app installs a subscriber
+/// or logger.
+macro_rules! report {
+    ($level:ident, $($args:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!(target: "actix_inertia", $($args)+);
+        #[cfg(not(feature = "tracing"))]
+        log::$level!(target: "actix_inertia", $($args)+);
+    };
+}
+
+/// Headers that carry credentials or session state.
+#[cfg(all(feature = "tracing", any(feature = "actix", feature = "axum")))]
+const SENSITIVE_HEADERS: [&str; 6] = [
+    "authorization",
+    "cookie",
+    "proxy-authorization",
+    "set-cookie",
+    "x-csrf-token",
+    "x-xsrf-token",
+];
+
+/// Headers as `name: value` for logging, with credentials and cookies redacted.
+#[cfg(all(feature = "tracing", any(feature = "actix", feature = "axum")))]
+pub(crate) fn redacted_headers<'a>(
+    headers: impl Iterator<Item = (&'a str, &'a [u8])>,
+) -> Vec<String> {
+    headers
+        .map(|(name, value)| {
+            if SENSITIVE_HEADERS
+                .iter()
+                .any(|sensitive| name.eq_ignore_ascii_case(sensitive))
+            {
+                format!("{name}: [redacted]")
+            } else {
+                format!("{name}: {}", String::from_utf8_lossy(value))
+            }
+        })
+        .collect()
+}
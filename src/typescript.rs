@@ -0,0 +1,144 @@
+//! TypeScript declarations for page props, generated with `ts-rs`.
+//!
+//! ```ignore
+//! #[derive(Serialize, TS)]
+//! struct HelloProps {
+//!     message: String,
+//! }
+//!
+//! PageTypes::new()
+//!     .shared::<SharedProps>()
+//!     .page::<HelloProps>("Hello")
+//!     .write("my-inertia-app/src/types/pages.d.ts")?;
+//! ```
+//!
+//! The generated file declares every props type and an `InertiaPages` interface mapping
+//! component names to them, so pages can use `defineProps<InertiaPages["Hello"]>()`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::{fs, io};
+use ts_rs::{Config, TypeVisitor, TS};
+
+const HEADER: &str = "// Generated by actix-inertia. Do not edit this file by hand.\n";
+
+/// Maps page components to their props types and renders them as a `.d.ts` file.
+pub struct PageTypes {
+    cfg: Config,
+    pages: BTreeMap<String, String>,
+    shared: Option<String>,
+    declarations: BTreeMap<String, String>,
+}
+
+impl Default for PageTypes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PageTypes {
+    pub fn new() -> Self {
+        Self::with_config(Config::default())
+    }
+
+    /// Uses a `ts-rs` [`Config`], e.g. to change how 64-bit integers are typed.
+    pub fn with_config(cfg: Config) -> Self {
+        Self {
+            cfg,
+            pages: BTreeMap::new(),
+            shared: None,
+            declarations: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `T` as the props of `component`.
+    pub fn page<T: TS + 'static>(mut self, component: &str) -> Self {
+        self.declare::<T>();
+        self.pages.insert(component.to_string(), T::name(&self.cfg));
+        self
+    }
+
+    /// Registers `T` as the shared props merged into every page.
+    pub fn shared<T: TS + 'static>(mut self) -> Self {
+        self.declare::<T>();
+        self.shared = Some(T::name(&self.cfg));
+        self
+    }
+
+    fn declare<T: TS + 'static>(&mut self) {
+        Declare {
+            cfg: &self.cfg,
+            declarations: &mut self.declarations,
+        }
+        .visit::<T>();
+    }
+
+    /// The contents of the `.d.ts` file.
+    pub fn render(&self) -> String {
+        let mut out = String::from(HEADER);
+
+        for declaration in self.declarations.values() {
+            out.push('\n');
+            out.push_str(declaration);
+            out.push('\n');
+        }
+
+        // The shared type is named inline, so it can't collide with a declared type.
+        out.push_str("\nexport interface InertiaPages {\n");
+        for (component, props) in &self.pages {
+            let component = serde_json::to_string(component).unwrap_or_default();
+            match &self.shared {
+                Some(shared) => out.push_str(&format!("  {component}: {props} & {shared};\n")),
+                None => out.push_str(&format!("  {component}: {props};\n")),
+            }
+        }
+        out.push_str("}\n");
+
+        out
+    }
+
+    /// Writes the declarations to `path`, creating parent directories as needed.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.render())
+    }
+
+    /// Fails when the file at `path` differs from the current declarations, e.g. in CI.
+    pub fn check(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if fs::read_to_string(path)? == self.render() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!(
+                "{} is out of date, regenerate it",
+                path.display()
+            )))
+        }
+    }
+}
+
+/// Collects the declaration of a type and, recursively, of every type it depends on.
+struct Declare<'a> {
+    cfg: &'a Config,
+    declarations: &'a mut BTreeMap<String, String>,
+}
+
+impl TypeVisitor for Declare<'_> {
+    fn visit<T: TS + 'static + ?Sized>(&mut self) {
+        // Built-in and wrapper types such as `Vec<User>` have no declaration of their own.
+        if T::output_path().is_some() {
+            let ident = T::ident(self.cfg);
+            if self.declarations.contains_key(&ident) {
+                return;
+            }
+            self.declarations
+                .insert(ident, format!("export {}", T::decl(self.cfg)));
+        }
+
+        T::visit_dependencies(self);
+        T::visit_generics(self);
+    }
+}
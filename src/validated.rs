@@ -0,0 +1,167 @@
+use crate::form::parse_form;
+use crate::precognition::{is_precognitive, precognition_parse_failure, precognition_response};
+use crate::validation::ValidationErrors;
+use actix_web::dev::Payload;
+use actix_web::error::InternalError;
+use actix_web::web::Bytes;
+use actix_web::{Error, FromRequest, HttpRequest};
+use futures_util::future::LocalBoxFuture;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+/// Validation library used by [`ValidatedInertiaForm`] to check a deserialized form.
+pub trait FormValidator<T> {
+    fn validate(value: &T) -> Result<(), ValidationErrors>;
+}
+
+/// Validates forms with the `validator` crate's `Validate` derive.
+#[cfg(feature = "validator")]
+pub struct Validator;
+
+#[cfg(feature = "validator")]
+impl<T: validator::Validate> FormValidator<T> for Validator {
+    fn validate(value: &T) -> Result<(), ValidationErrors> {
+        value.validate().map_err(|report| {
+            let mut errors = ValidationErrors::new();
+            collect_validator_errors(&mut errors, None, &report);
+            errors
+        })
+    }
+}
+
+#[cfg(feature = "validator")]
+fn collect_validator_errors(
+    errors: &mut ValidationErrors,
+    prefix: Option<&str>,
+    report: &validator::ValidationErrors,
+) {
+    use validator::ValidationErrorsKind;
+
+    for (field, kind) in report.errors() {
+        let path = match prefix {
+            Some(prefix) => format!("{prefix}.{field}"),
+            None => field.to_string(),
+        };
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                if let Some(error) = field_errors.first() {
+                    let message = match &error.message {
+                        Some(message) => message.to_string(),
+                        None => format!("The {field} field is invalid."),
+                    };
+                    errors.add(path, message);
+                }
+            }
+            ValidationErrorsKind::Struct(nested) => {
+                collect_validator_errors(errors, Some(&path), nested)
+            }
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_validator_errors(errors, Some(&format!("{path}.{index}")), nested);
+                }
+            }
+        }
+    }
+}
+
+/// Validates forms with the `garde` crate's `Validate` derive.
+#[cfg(feature = "garde")]
+pub struct Garde;
+
+#[cfg(feature = "garde")]
+impl<T> FormValidator<T> for Garde
+where
+    T: garde::Validate,
+    T::Context: Default,
+{
+    fn validate(value: &T) -> Result<(), ValidationErrors> {
+        value.validate().map_err(|report| {
+            report
+                .iter()
+                .map(|(path, error)| {
+                    // garde writes `items[0].name`, Inertia expects `items.0.name`.
+                    let field = path.to_string().replace('[', ".").replace(']', "");
+                    (field, error.message().to_string())
+                })
+                .collect()
+        })
+    }
+}
+
+#[cfg(feature = "validator")]
+pub type DefaultValidator = Validator;
+
+#[cfg(all(feature = "garde", not(feature = "validator")))]
+pub type DefaultValidator = Garde;
+
+/// An [`InertiaForm`](crate::InertiaForm) that is also validated before reaching the handler.
+///
+/// Failing fields are flashed back to the previous page as the flat `{field: message}`
+/// `errors` prop, nested under the `X-Inertia-Error-Bag` when the client sends one. Nested
+/// fields use dotted paths such as `address.city` and `items.0.name`.
+///
+/// Precognition requests are answered by the extractor itself with `204` or `422`, so the
+/// handler only runs for real submissions.
+///
+/// `V` picks the validation library. It defaults to `validator` when that feature is enabled
+/// and to `garde` otherwise; use `ValidatedInertiaForm<T, Garde>` when both are enabled.
+pub struct ValidatedInertiaForm<T, V = DefaultValidator>(pub T, PhantomData<V>);
+
+impl<T, V> ValidatedInertiaForm<T, V> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, V> Deref for ValidatedInertiaForm<T, V> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, V> DerefMut for ValidatedInertiaForm<T, V> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T, V> FromRequest for ValidatedInertiaForm<T, V>
+where
+    T: DeserializeOwned + 'static,
+    V: FormValidator<T> + 'static,
+{
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let body = Bytes::from_request(&req, payload);
+
+        Box::pin(async move {
+            let body = body.await?;
+            let parsed = parse_form::<T>(&req, body).await?;
+
+            if is_precognitive(&req) {
+                let response = match &parsed {
+                    Ok(value) => precognition_response(&req, V::validate(value)),
+                    Err(errors) => precognition_parse_failure(errors),
+                };
+                return Err(InternalError::from_response("precognition request", response).into());
+            }
+
+            let result = parsed.and_then(|value| V::validate(&value).map(|_| value));
+
+            match result {
+                Ok(value) => Ok(ValidatedInertiaForm(value, PhantomData)),
+                Err(errors) => Err(InternalError::from_response(
+                    "invalid form data",
+                    errors.redirect_back(&req),
+                )
+                .into()),
+            }
+        })
+    }
+}
@@ -0,0 +1,120 @@
+use crate::protocol;
+use crate::X_INERTIA_ERROR_BAG;
+use actix_web::cookie::{time::Duration, Cookie};
+use actix_web::{http, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub use crate::protocol::ERRORS_COOKIE;
+
+/// Field errors in the flat `{field: message}` shape Inertia's `useForm` expects.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationErrors(BTreeMap<String, String>);
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an error for `field`. Only the first message per field is kept.
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.0.entry(field.into()).or_insert_with(|| message.into());
+    }
+
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.0.get(field).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(field, message)| (field.as_str(), message.as_str()))
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&str, &str) -> bool) {
+        self.0.retain(|field, message| keep(field, message));
+    }
+
+    /// The `errors` prop for `req`, nested under the `X-Inertia-Error-Bag` when one is sent.
+    pub fn to_value(&self, req: &HttpRequest) -> serde_json::Value {
+        let errors = serde_json::to_value(&self.0).unwrap_or_default();
+        match error_bag(req) {
+            Some(bag) => {
+                let mut bagged = serde_json::Map::new();
+                bagged.insert(bag, errors);
+                serde_json::Value::Object(bagged)
+            }
+            None => errors,
+        }
+    }
+
+    /// Redirects back to the previous page, which will receive these errors as its `errors` prop.
+    pub fn redirect_back(&self, req: &HttpRequest) -> HttpResponse {
+        let mut response = redirect_back(req);
+        let cookie = Cookie::build(
+            ERRORS_COOKIE,
+            protocol::encode_flashed_errors(&self.to_value(req)),
+        )
+        .path("/")
+        .http_only(true)
+        .finish();
+        let _ = response.add_cookie(&cookie);
+        response
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for ValidationErrors {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut errors = Self::new();
+        for (field, message) in iter {
+            errors.add(field, message);
+        }
+        errors
+    }
+}
+
+/// A `303 See Other` to the `Referer`, or to `/` when the client did not send one.
+pub fn redirect_back(req: &HttpRequest) -> HttpResponse {
+    let location = req
+        .headers()
+        .get(http::header::REFERER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("/");
+
+    HttpResponse::SeeOther()
+        .append_header((http::header::LOCATION, location))
+        .finish()
+}
+
+/// Errors flashed by [`ValidationErrors::redirect_back`] on the previous request, if any.
+pub fn flashed_errors(req: &HttpRequest) -> Option<serde_json::Value> {
+    req.headers()
+        .get_all(http::header::COOKIE)
+        .filter_map(|cookies| cookies.to_str().ok())
+        .find_map(protocol::flashed_errors)
+}
+
+/// Expires the flashed errors so they are only shown once.
+pub(crate) fn forget_flashed_errors(response: &mut HttpResponse) {
+    let cookie = Cookie::build(ERRORS_COOKIE, "")
+        .path("/")
+        .max_age(Duration::ZERO)
+        .finish();
+    let _ = response.add_cookie(&cookie);
+}
+
+fn error_bag(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(X_INERTIA_ERROR_BAG)
+        .and_then(|v| v.to_str().ok())
+        .filter(|bag| !bag.is_empty())
+        .map(str::to_string)
+}
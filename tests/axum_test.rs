@@ -0,0 +1,248 @@
+#![cfg(feature = "axum")]
+
+use actix_inertia::axum::InertiaLayer;
+use actix_inertia::{
+    CacheControl, Inertia, InertiaRequest, ResponseFactory, X_INERTIA, X_INERTIA_LOCATION,
+    X_INERTIA_PARTIAL_COMPONENT, X_INERTIA_PARTIAL_EXCEPT, X_INERTIA_PARTIAL_ONLY,
+    X_INERTIA_VERSION,
+};
+use axum::body::{to_bytes, Body};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::routing::{get, post, put};
+use axum::Router;
+use http::header::{CONTENT_TYPE, SET_COOKIE};
+use http::{Request, StatusCode};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tower::ServiceExt;
+
+async fn handler(request: InertiaRequest) -> Inertia<Value> {
+    Inertia::new(
+        "ComponentName".to_string(),
+        json!({"foo": "foo", "bar": "bar"}),
+        request.url,
+    )
+}
+
+fn app() -> Router {
+    let mut factory = ResponseFactory::new();
+    factory.set_version(|| "example-version".to_string());
+
+    Router::new()
+        .route("/foo", get(handler))
+        .route("/update", put(|| async { Redirect::to("/foo") }))
+        .layer(InertiaLayer::new(factory))
+}
+
+async fn send(request: http::request::Builder) -> Response {
+    app()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn json(response: Response) -> Value {
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn inertia_get(uri: &str) -> http::request::Builder {
+    Request::get(uri)
+        .header(X_INERTIA, "true")
+        .header(X_INERTIA_VERSION, "example-version")
+}
+
+#[tokio::test]
+async fn test_the_view_is_served_by_inertia() {
+    let response = send(inertia_get("/foo")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[X_INERTIA], "true");
+    let body = json(response).await;
+    assert_eq!(body["component"], "ComponentName");
+    assert_eq!(body["url"], "/foo");
+    assert_eq!(body["version"], "example-version");
+}
+
+#[tokio::test]
+async fn test_the_view_is_not_served_by_inertia() {
+    let response = send(Request::get("/foo")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key(X_INERTIA));
+}
+
+#[tokio::test]
+async fn test_the_asset_version_does_not_match() {
+    let response = send(
+        Request::get("/foo?page=2")
+            .header(X_INERTIA, "true")
+            .header(X_INERTIA_VERSION, "different-version"),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(response.headers()[X_INERTIA_LOCATION], "/foo?page=2");
+}
+
+#[tokio::test]
+async fn test_partial_reload_only_returns_requested_props() {
+    let response = send(
+        inertia_get("/foo")
+            .header(X_INERTIA_PARTIAL_COMPONENT, "ComponentName")
+            .header(X_INERTIA_PARTIAL_ONLY, "foo"),
+    )
+    .await;
+
+    let body = json(response).await;
+    assert!(body["props"].get("foo").is_some());
+    assert!(body["props"].get("bar").is_none());
+}
+
+#[tokio::test]
+async fn test_partial_reload_excludes_props() {
+    let response = send(
+        inertia_get("/foo")
+            .header(X_INERTIA_PARTIAL_COMPONENT, "ComponentName")
+            .header(X_INERTIA_PARTIAL_EXCEPT, "bar"),
+    )
+    .await;
+
+    let body = json(response).await;
+    assert!(body["props"].get("foo").is_some());
+    assert!(body["props"].get("bar").is_none());
+}
+
+#[tokio::test]
+async fn test_redirects_after_put_become_see_other() {
+    let response = send(Request::put("/update").header(X_INERTIA, "true")).await;
+
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+}
+
+#[tokio::test]
+async fn test_shared_props_and_flashed_errors_are_added() {
+    let app = Router::new()
+        .route("/foo", get(handler))
+        .layer(InertiaLayer::new(ResponseFactory::new()).share(|_| json!({"user": "Ada"})));
+
+    let response = app
+        .oneshot(
+            Request::get("/foo")
+                .header(X_INERTIA, "true")
+                .header("Cookie", "inertia_errors={\"name\":\"Required\"}")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(response.headers()["set-cookie"]
+        .to_str()
+        .unwrap()
+        .starts_with("inertia_errors=;"));
+    let body = json(response).await;
+    assert_eq!(body["props"]["user"], "Ada");
+    assert_eq!(body["props"]["errors"]["name"], "Required");
+}
+
+#[tokio::test]
+async fn test_pages_without_the_layer_are_an_error() {
+    // With the actix feature on, the inherent `Inertia::into_response` takes precedence.
+    let response = IntoResponse::into_response(Inertia::new(
+        "ComponentName".to_string(),
+        json!({}),
+        "/".to_string(),
+    ));
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn test_prefetch_responses_get_cache_hints() {
+    let response = send(inertia_get("/foo").header("Purpose", "prefetch")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key("cache-control"));
+
+    let mut factory = ResponseFactory::new();
+    factory.set_prefetch_cache(Some(
+        CacheControl::private().max_age(Duration::from_secs(30)),
+    ));
+    let response = Router::new()
+        .route("/foo", get(handler))
+        .layer(InertiaLayer::new(factory))
+        .oneshot(
+            inertia_get("/foo")
+                .header("Purpose", "prefetch")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["cache-control"], "private, max-age=30");
+    assert!(response.headers()["vary"]
+        .to_str()
+        .unwrap()
+        .ends_with("Purpose, Sec-Purpose"));
+}
+
+#[tokio::test]
+async fn test_unchanged_pages_are_not_modified() {
+    let response = send(inertia_get("/foo")).await;
+    let etag = response.headers()["etag"].clone();
+
+    let response = send(inertia_get("/foo").header("If-None-Match", etag.clone())).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()["etag"], etag);
+}
+
+#[tokio::test]
+async fn test_responses_vary_on_x_inertia() {
+    let page = send(Request::get("/foo")).await;
+    let conflict = send(
+        Request::get("/foo")
+            .header(X_INERTIA, "true")
+            .header(X_INERTIA_VERSION, "different-version"),
+    )
+    .await;
+
+    assert!(page.headers()["vary"]
+        .to_str()
+        .unwrap()
+        .starts_with("X-Inertia,"));
+    assert_eq!(conflict.headers()["vary"], "X-Inertia");
+}
+
+#[tokio::test]
+async fn test_status_and_headers_returned_with_the_page_are_kept() {
+    let app = Router::new()
+        .route(
+            "/posts",
+            post(|request: InertiaRequest| async move {
+                (
+                    [(SET_COOKIE, "flash=created; Path=/")],
+                    (StatusCode::CREATED, handler(request).await),
+                )
+            }),
+        )
+        .layer(InertiaLayer::new(ResponseFactory::new()));
+
+    let response = app
+        .oneshot(
+            Request::post("/posts")
+                .header(X_INERTIA, "true")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[SET_COOKIE], "flash=created; Path=/");
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+    assert_eq!(response.headers()[X_INERTIA], "true");
+    assert_eq!(json(response).await["component"], "ComponentName");
+}
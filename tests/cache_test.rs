@@ -0,0 +1,124 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, CacheControl, ResponseFactory, VersionMiddleware, PURPOSE,
+    X_INERTIA, X_INERTIA_VERSION,
+};
+use actix_web::{http, test, web, App, HttpRequest};
+use serde_json::json;
+use std::time::Duration;
+
+async fn handler(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("Dashboard", json!({"visits": 10}))
+        .respond_to(&req)
+        .await
+}
+
+macro_rules! app {
+    ($factory:expr) => {
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new($factory))
+                .service(web::resource("/dashboard").to(handler))
+                .service(
+                    web::scope("/versioned")
+                        .wrap(VersionMiddleware::new("1".to_string()))
+                        .service(web::resource("").to(handler)),
+                ),
+        )
+        .await
+    };
+}
+
+fn vary(resp: &actix_web::dev::ServiceResponse) -> String {
+    resp.headers()
+        .get_all(http::header::VARY)
+        .map(|v| v.to_str().unwrap())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[actix_web::test]
+async fn test_page_responses_vary_on_inertia_headers() {
+    let app = app!(ResponseFactory::new());
+
+    for req in [
+        test::TestRequest::get().uri("/dashboard"),
+        test::TestRequest::get()
+            .uri("/dashboard")
+            .insert_header((X_INERTIA, "true")),
+    ] {
+        let resp = test::call_service(&app, req.to_request()).await;
+        let vary = vary(&resp);
+
+        assert!(vary.starts_with("X-Inertia,"), "{vary}");
+        assert!(vary.contains("X-Inertia-Partial-Data"));
+        assert!(vary.contains("X-Inertia-Partial-Except"));
+    }
+}
+
+#[actix_web::test]
+async fn test_version_conflicts_vary_on_x_inertia() {
+    let app = app!(ResponseFactory::new());
+
+    let req = test::TestRequest::get()
+        .uri("/versioned")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "0"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+    assert_eq!(vary(&resp), "X-Inertia");
+}
+
+#[actix_web::test]
+async fn test_no_cache_control_by_default() {
+    let app = app!(ResponseFactory::new());
+
+    let req = test::TestRequest::get().uri("/dashboard").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert!(!resp.headers().contains_key(http::header::CACHE_CONTROL));
+}
+
+#[actix_web::test]
+async fn test_cache_control_policies_for_html_and_json() {
+    let mut factory = ResponseFactory::new();
+    factory.set_html_cache(Some(CacheControl::no_store()));
+    factory.set_json_cache(Some(
+        CacheControl::private().max_age(Duration::from_secs(5)),
+    ));
+    factory.set_prefetch_cache(Some(
+        CacheControl::private().max_age(Duration::from_secs(30)),
+    ));
+    let app = app!(factory);
+
+    let cache_control = |resp: actix_web::dev::ServiceResponse| {
+        resp.headers()
+            .get(http::header::CACHE_CONTROL)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string()
+    };
+
+    let req = test::TestRequest::get().uri("/dashboard").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(cache_control(resp), "no-store");
+
+    let req = test::TestRequest::get()
+        .uri("/dashboard")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(cache_control(resp), "private, max-age=5");
+
+    let req = test::TestRequest::get()
+        .uri("/dashboard")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((PURPOSE, "prefetch"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(cache_control(resp), "private, max-age=30");
+}
@@ -0,0 +1,177 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{InertiaConfig, InertiaError, RootView, SsrConfig};
+use std::fs;
+use std::path::PathBuf;
+
+fn root_view(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(name);
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[actix_web::test]
+async fn test_build_requires_a_root_view() {
+    let result = InertiaConfig::new().build();
+
+    assert!(matches!(result, Err(InertiaError::Config(_))));
+}
+
+#[actix_web::test]
+async fn test_build_rejects_a_missing_root_view() {
+    let result = InertiaConfig::new()
+        .root_view("does/not/exist.html")
+        .build();
+
+    assert!(matches!(result, Err(InertiaError::Config(_))));
+}
+
+#[actix_web::test]
+async fn test_build_rejects_a_root_view_without_placeholder() {
+    let path = root_view("actix-inertia-config-no-placeholder.html", "<div></div>");
+
+    let result = InertiaConfig::new().root_view(&path).build();
+
+    assert!(matches!(result, Err(InertiaError::Config(_))));
+}
+
+#[actix_web::test]
+async fn test_build_creates_a_configured_factory() {
+    let path = root_view(
+        "actix-inertia-config-app.html",
+        "<div id=\"app\" data-page=\"{{DATA_PAGE}}\"></div>",
+    );
+
+    let factory = InertiaConfig::new()
+        .root_view(&path)
+        .version("1.2.3")
+        .encrypt_history(true)
+        .errors_key("failures")
+        .dev_mode(false)
+        .build()
+        .unwrap();
+
+    assert_eq!(factory.get_version(), "1.2.3");
+    assert!(factory.encrypt_history());
+    assert_eq!(factory.errors_key(), "failures");
+    assert!(!factory.dev_mode());
+    assert!(factory.ssr().is_none());
+}
+
+#[actix_web::test]
+async fn test_version_manifest_is_hashed() {
+    let view = root_view("actix-inertia-config-manifest.html", "{{DATA_PAGE}}");
+    let manifest = root_view("actix-inertia-config-manifest.json", "{\"app.js\": {}}");
+
+    let factory = InertiaConfig::new()
+        .root_view(&view)
+        .version_manifest(&manifest)
+        .build()
+        .unwrap();
+
+    // 64-bit FNV-1a of the manifest, the same on every build and toolchain.
+    assert_eq!(factory.get_version(), "733e80ca260383eb");
+}
+
+#[actix_web::test]
+async fn test_vars_override_settings() {
+    let path = root_view("actix-inertia-config-env.html", "{{DATA_PAGE}}");
+
+    let factory = InertiaConfig::new()
+        .vars([
+            ("INERTIA_ROOT_VIEW", path.to_string_lossy().to_string()),
+            ("INERTIA_VERSION", "abc".to_string()),
+            ("INERTIA_ENCRYPT_HISTORY", "true".to_string()),
+            ("PATH", "ignored".to_string()),
+            ("INERTIA_UNRELATED_TOOL", "ignored".to_string()),
+        ])
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(factory.get_version(), "abc");
+    assert!(factory.encrypt_history());
+}
+
+#[actix_web::test]
+async fn test_vars_reject_both_version_sources() {
+    let result = InertiaConfig::new().vars([
+        ("INERTIA_VERSION_MANIFEST", "public/build/manifest.json"),
+        ("INERTIA_VERSION", "abc"),
+    ]);
+    assert!(matches!(result, Err(InertiaError::Config(_))));
+}
+
+#[actix_web::test]
+async fn test_build_rejects_ssr_until_it_is_supported() {
+    let path = root_view("actix-inertia-config-ssr.html", "{{DATA_PAGE}}");
+
+    let result = InertiaConfig::new()
+        .root_view(&path)
+        .vars([("INERTIA_SSR_ENABLED", "1")])
+        .unwrap()
+        .build();
+    assert!(matches!(result, Err(InertiaError::Config(_))));
+
+    let result = InertiaConfig::new()
+        .root_view(&path)
+        .ssr(SsrConfig {
+            enabled: true,
+            ..SsrConfig::default()
+        })
+        .build();
+    assert!(matches!(result, Err(InertiaError::Config(_))));
+}
+
+#[actix_web::test]
+async fn test_vars_reject_invalid_values() {
+    let result = InertiaConfig::new().vars([("INERTIA_DEV_MODE", "maybe")]);
+    assert!(matches!(result, Err(InertiaError::Config(_))));
+}
+
+#[cfg(feature = "toml")]
+#[actix_web::test]
+async fn test_from_file_reads_toml() {
+    let view = root_view("actix-inertia-config-toml.html", "{{DATA_PAGE}}");
+    let file = root_view(
+        "actix-inertia-config.toml",
+        &format!(
+            "root_view = {:?}\nversion = \"2\"\nclear_history = true\n",
+            view.to_string_lossy()
+        ),
+    );
+
+    let factory = InertiaConfig::from_file(&file).unwrap().build().unwrap();
+
+    assert_eq!(factory.get_version(), "2");
+    assert!(factory.clear_history());
+}
+
+#[cfg(feature = "toml")]
+#[actix_web::test]
+async fn test_toml_rejects_unknown_keys() {
+    let result = InertiaConfig::new().toml("root_veiw = \"app.html\"");
+
+    assert!(matches!(result, Err(InertiaError::Config(_))));
+}
+
+#[actix_web::test]
+async fn test_build_checks_named_root_views() {
+    let path = root_view("actix-inertia-config-default.html", "{{DATA_PAGE}}");
+
+    let result = InertiaConfig::new()
+        .root_view(&path)
+        .add_root_view("admin", RootView::new("does/not/exist.html"))
+        .build();
+    assert!(matches!(result, Err(InertiaError::Config(_))));
+
+    let factory = InertiaConfig::new()
+        .root_view(&path)
+        .add_root_view("admin", RootView::new(path.to_string_lossy()).version("a"))
+        .build()
+        .unwrap();
+    assert_eq!(
+        factory.get_root_view("admin").unwrap().get_version(),
+        Some("a".to_string())
+    );
+}
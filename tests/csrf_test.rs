@@ -0,0 +1,146 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::csrf::{XSRF_TOKEN_COOKIE, X_XSRF_TOKEN};
+use actix_inertia::{
+    inertia_responder::InertiaResponder, CsrfFailure, CsrfMiddleware, ResponseFactory, X_INERTIA,
+};
+use actix_web::cookie::Cookie;
+use actix_web::{http, test, web, App, HttpRequest, HttpResponse};
+use serde_json::Value;
+
+async fn form(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("Form", serde_json::json!({}))
+        .respond_to(&req)
+        .await
+}
+
+async fn store() -> HttpResponse {
+    HttpResponse::SeeOther()
+        .append_header((http::header::LOCATION, "/form"))
+        .finish()
+}
+
+#[actix_web::test]
+async fn test_safe_requests_receive_a_token_cookie() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(CsrfMiddleware::new())
+            .service(web::resource("/form").to(form)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/form").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    let cookie = resp
+        .response()
+        .cookies()
+        .find(|c| c.name() == XSRF_TOKEN_COOKIE)
+        .expect("token cookie is set");
+    assert_eq!(cookie.value().len(), 40);
+    assert_eq!(cookie.http_only(), None);
+}
+
+#[actix_web::test]
+async fn test_matching_token_is_accepted() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(CsrfMiddleware::new())
+            .service(web::resource("/form").route(web::post().to(store))),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/form")
+        .cookie(Cookie::new(XSRF_TOKEN_COOKIE, "secret-token"))
+        .insert_header((X_XSRF_TOKEN, "secret-token"))
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::SEE_OTHER);
+}
+
+#[actix_web::test]
+async fn test_mismatched_token_answers_page_expired() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(CsrfMiddleware::new())
+            .service(web::resource("/form").route(web::post().to(store))),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/form")
+        .cookie(Cookie::new(XSRF_TOKEN_COOKIE, "secret-token"))
+        .insert_header((X_XSRF_TOKEN, "forged-token"))
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status().as_u16(), 419);
+}
+
+#[actix_web::test]
+async fn test_excluded_paths_are_not_checked() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(CsrfMiddleware::new().exclude("/webhooks"))
+            .service(web::resource("/webhooks/stripe").route(web::post().to(store))),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/webhooks/stripe")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::SEE_OTHER);
+}
+
+#[actix_web::test]
+async fn test_mismatched_token_can_redirect_back_with_errors() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(CsrfMiddleware::new().on_failure(CsrfFailure::RedirectBack))
+            .service(
+                web::resource("/form")
+                    .route(web::get().to(form))
+                    .route(web::post().to(store)),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/form")
+        .insert_header((http::header::REFERER, "/form"))
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::SEE_OTHER);
+    assert_eq!(resp.headers().get(http::header::LOCATION).unwrap(), "/form");
+    let cookies: Vec<Cookie> = resp.response().cookies().map(|c| c.into_owned()).collect();
+    let errors = cookies
+        .iter()
+        .find(|c| c.name() == actix_inertia::validation::ERRORS_COOKIE)
+        .expect("errors are flashed")
+        .clone();
+
+    let req = test::TestRequest::get()
+        .uri("/form")
+        .cookie(errors)
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    let body: Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+    assert!(body["props"]["errors"]["csrf"].is_string());
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "macros")]
+
+use actix_inertia::{InertiaPage, PropOptions, ResponseFactory, X_INERTIA};
+use actix_web::{test, web, App};
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Serialize, InertiaPage)]
+#[inertia(component = "Hello")]
+struct HelloProps {
+    message: String,
+}
+
+#[derive(Serialize, InertiaPage)]
+#[inertia(component = "Users/Show")]
+#[serde(rename_all = "camelCase")]
+struct ShowUser {
+    user_name: String,
+    #[inertia(lazy)]
+    recent_activity: Vec<String>,
+    #[inertia(always)]
+    #[serde(rename = "flashMessage", skip_serializing_if = "Option::is_none")]
+    flash: Option<String>,
+    #[inertia(deferred = "sidebar")]
+    teams: Vec<String>,
+    #[inertia(deferred)]
+    permissions: Vec<String>,
+    #[inertia(merge)]
+    comments: Vec<u32>,
+}
+
+async fn hello() -> HelloProps {
+    HelloProps {
+        message: "this is my message from Rust :)".to_string(),
+    }
+}
+
+#[actix_web::test]
+async fn test_field_attributes_map_onto_prop_options() {
+    assert_eq!(ShowUser::COMPONENT, "Users/Show");
+    assert_eq!(
+        ShowUser::prop_options(),
+        PropOptions::new()
+            .lazy("recentActivity")
+            .always("flashMessage")
+            .defer("teams", "sidebar")
+            .defer("permissions", "default")
+            .merge("comments")
+    );
+}
+
+#[actix_web::test]
+async fn test_props_struct_is_returnable_from_a_handler() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .service(web::resource("/hello").to(hello)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/hello")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+
+    assert_eq!(body["component"], "Hello");
+    assert_eq!(body["props"]["message"], "this is my message from Rust :)");
+    assert_eq!(body["url"], "/hello");
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, ResponseFactory, X_INERTIA,
+    X_INERTIA_DEBUG_FILTERED_PROPS, X_INERTIA_ERROR_BAG, X_INERTIA_PARTIAL_COMPONENT,
+    X_INERTIA_PARTIAL_EXCEPT, X_INERTIA_PARTIAL_ONLY,
+};
+use actix_web::{http, test, web, App, HttpRequest};
+use serde_json::json;
+
+async fn handler(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("Dashboard", json!({"visits": 10, "sales": 3, "users": []}))
+        .lazy("users")
+        .respond_to(&req)
+        .await
+}
+
+macro_rules! app {
+    ($factory:expr) => {
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new($factory))
+                .service(web::resource("/dashboard").to(handler)),
+        )
+        .await
+    };
+}
+
+fn partial_reload() -> test::TestRequest {
+    test::TestRequest::get()
+        .uri("/dashboard")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_ERROR_BAG, "login"))
+        .insert_header((X_INERTIA_PARTIAL_COMPONENT, "Dashboard"))
+        .insert_header((X_INERTIA_PARTIAL_ONLY, "visits"))
+}
+
+#[actix_web::test]
+async fn test_request_headers_are_not_echoed() {
+    let app = app!(ResponseFactory::new());
+
+    let resp = test::call_service(&app, partial_reload().to_request()).await;
+
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    assert_eq!(resp.headers().get(X_INERTIA).unwrap(), "true");
+    for name in [
+        X_INERTIA_ERROR_BAG,
+        X_INERTIA_PARTIAL_COMPONENT,
+        X_INERTIA_PARTIAL_ONLY,
+        X_INERTIA_PARTIAL_EXCEPT,
+        X_INERTIA_DEBUG_FILTERED_PROPS,
+    ] {
+        assert!(!resp.headers().contains_key(name), "{name} was sent");
+    }
+}
+
+#[actix_web::test]
+async fn test_diagnostic_mode_echoes_headers_and_reports_filtered_props() {
+    let mut factory = ResponseFactory::new();
+    factory.set_diagnostics(true);
+    let app = app!(factory);
+
+    let resp = test::call_service(&app, partial_reload().to_request()).await;
+
+    assert_eq!(resp.headers().get(X_INERTIA_ERROR_BAG).unwrap(), "login");
+    assert_eq!(
+        resp.headers().get(X_INERTIA_PARTIAL_COMPONENT).unwrap(),
+        "Dashboard"
+    );
+    assert_eq!(
+        resp.headers().get(X_INERTIA_PARTIAL_ONLY).unwrap(),
+        "visits"
+    );
+    assert_eq!(
+        resp.headers().get(X_INERTIA_DEBUG_FILTERED_PROPS).unwrap(),
+        "sales,users"
+    );
+}
+
+#[actix_web::test]
+async fn test_diagnostic_mode_reports_lazy_props_on_first_visits() {
+    let mut factory = ResponseFactory::new();
+    factory.set_diagnostics(true);
+    let app = app!(factory);
+
+    let req = test::TestRequest::get()
+        .uri("/dashboard")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(
+        resp.headers().get(X_INERTIA_DEBUG_FILTERED_PROPS).unwrap(),
+        "users"
+    );
+    assert!(!resp.headers().contains_key(X_INERTIA_PARTIAL_ONLY));
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{ErrorPages, ResponseFactory, X_INERTIA};
+use actix_web::{error, http, test, web, App, HttpResponse};
+use serde_json::Value;
+
+async fn broken() -> Result<HttpResponse, actix_web::Error> {
+    Err(error::ErrorInternalServerError("database is down"))
+}
+
+async fn maintenance() -> HttpResponse {
+    HttpResponse::ServiceUnavailable()
+        .insert_header((http::header::RETRY_AFTER, "120"))
+        .cookie(actix_web::cookie::Cookie::new("XSRF-TOKEN", "fresh"))
+        .body("down for maintenance")
+}
+
+async fn teapot() -> HttpResponse {
+    HttpResponse::build(http::StatusCode::IM_A_TEAPOT).finish()
+}
+
+#[actix_web::test]
+async fn test_missing_routes_render_the_error_component() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(ErrorPages::new("Error")),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/missing")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    assert_eq!(resp.headers().get(X_INERTIA).unwrap(), "true");
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["component"], "Error");
+    assert_eq!(body["props"]["status"], 404);
+    assert_eq!(body["url"], "/missing");
+}
+
+#[actix_web::test]
+async fn test_handler_errors_include_details_when_enabled() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(ErrorPages::new("Error").show_details(true))
+            .service(web::resource("/broken").to(broken)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/broken")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["props"]["message"], "database is down");
+}
+
+#[actix_web::test]
+async fn test_handler_errors_are_generic_without_details() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(ErrorPages::new("Error").show_details(false))
+            .service(web::resource("/broken").to(broken)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/broken").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["component"], "Error");
+    assert_eq!(
+        body["props"]["message"],
+        "Whoops, something went wrong on our servers."
+    );
+}
+
+#[actix_web::test]
+async fn test_unconfigured_statuses_are_left_alone() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(ErrorPages::new("Error").only(&[404]))
+            .service(web::resource("/teapot").to(teapot))
+            .service(web::resource("/broken").to(broken)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/teapot").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::IM_A_TEAPOT);
+    assert!(!resp.headers().contains_key(X_INERTIA));
+
+    let req = test::TestRequest::get().uri("/broken").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(test::read_body(resp).await, "database is down");
+}
+
+#[actix_web::test]
+async fn test_error_headers_are_kept() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(ErrorPages::new("Error"))
+            .service(web::resource("/maintenance").to(maintenance)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/maintenance")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        resp.headers().get(http::header::RETRY_AFTER).unwrap(),
+        "120"
+    );
+    assert_eq!(
+        resp.headers().get(http::header::SET_COOKIE).unwrap(),
+        "XSRF-TOKEN=fresh"
+    );
+    assert_eq!(
+        resp.headers().get(http::header::CONTENT_TYPE).unwrap(),
+        "application/json"
+    );
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["props"]["status"], 503);
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{Inertia, InertiaError, ResponseFactory, X_INERTIA, X_INERTIA_VERSION};
+use actix_web::{http, test, web, App, HttpRequest, HttpResponse};
+use serde::{Serialize, Serializer};
+use std::fs;
+
+struct Unserializable;
+
+impl Serialize for Unserializable {
+    fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom("not today"))
+    }
+}
+
+async fn broken(req: HttpRequest) -> HttpResponse {
+    Inertia::new("Broken".to_string(), Unserializable, req.uri().to_string())
+        .into_response(&req)
+        .await
+}
+
+async fn broken_factory(req: HttpRequest, factory: web::Data<ResponseFactory>) -> HttpResponse {
+    factory
+        .render("Broken", Unserializable, &req.uri().to_string())
+        .into_response(&req)
+        .await
+}
+
+#[actix_web::test]
+async fn test_unserializable_props_render_a_server_error() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .route("/broken", web::get().to(broken))
+            .route("/factory", web::get().to(broken_factory)),
+    )
+    .await;
+
+    for uri in ["/broken", "/factory"] {
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .insert_header((X_INERTIA, "true"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+        let body = test::read_body(resp).await;
+        if cfg!(debug_assertions) {
+            assert!(String::from_utf8_lossy(&body).contains("not today"));
+        }
+    }
+}
+
+#[actix_web::test]
+async fn test_try_into_response_returns_the_error() {
+    let req = test::TestRequest::get()
+        .uri("/broken")
+        .app_data(web::Data::new(ResponseFactory::new()))
+        .to_http_request();
+
+    let result = Inertia::new("Broken".to_string(), Unserializable, "/broken".to_string())
+        .try_into_response(&req)
+        .await;
+
+    assert!(matches!(result, Err(InertiaError::Serialization(_))));
+}
+
+#[actix_web::test]
+async fn test_try_render_returns_the_error() {
+    let factory = ResponseFactory::new();
+
+    let result = factory.try_render("Broken", Unserializable, "/broken");
+
+    assert!(matches!(result, Err(InertiaError::Serialization(_))));
+}
+
+#[actix_web::test]
+async fn test_root_view_without_placeholder_is_a_template_error() {
+    let path = std::env::temp_dir().join("actix-inertia-no-placeholder.html");
+    fs::write(&path, "<div id=\"app\"></div>").unwrap();
+    let mut factory = ResponseFactory::new();
+    factory.set_root_view(path.to_str().unwrap());
+
+    let result = factory.try_render_root("{}");
+
+    assert!(matches!(result, Err(InertiaError::Template(_))));
+    fs::remove_file(path).unwrap();
+}
+
+#[actix_web::test]
+async fn test_missing_factory_is_an_error_in_debug_builds() {
+    let req = test::TestRequest::get().uri("/").to_http_request();
+
+    let result = Inertia::new("Home".to_string(), (), "/".to_string())
+        .try_into_response(&req)
+        .await;
+
+    if cfg!(debug_assertions) {
+        assert!(matches!(result, Err(InertiaError::MissingFactory)));
+    } else {
+        assert!(result.is_ok());
+    }
+}
+
+async fn version(req: HttpRequest) -> HttpResponse {
+    Inertia::new("Version".to_string(), (), req.uri().to_string())
+        .into_response(&req)
+        .await
+}
+
+fn versioned(version: &'static str) -> ResponseFactory {
+    let mut factory = ResponseFactory::new();
+    factory.set_version(move || version.to_string());
+    factory
+}
+
+#[actix_web::test]
+async fn test_factory_is_found_through_scope_app_data() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(versioned("app")))
+            .route("/", web::get().to(version))
+            .service(
+                web::scope("/admin")
+                    .app_data(web::Data::new(versioned("admin")))
+                    .route("", web::get().to(version)),
+            ),
+    )
+    .await;
+
+    for (uri, expected) in [("/", "app"), ("/admin", "admin")] {
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .insert_header((X_INERTIA, "true"))
+            .insert_header((X_INERTIA_VERSION, expected))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["version"], expected);
+    }
+}
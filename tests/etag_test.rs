@@ -0,0 +1,132 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, ResponseFactory, X_INERTIA, X_INERTIA_PARTIAL_COMPONENT,
+    X_INERTIA_PARTIAL_ONLY, X_INERTIA_VERSION,
+};
+use actix_web::{http, test, web, App, HttpRequest};
+use serde_json::json;
+
+async fn handler(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("Dashboard", json!({"visits": 10, "sales": 3}))
+        .respond_to(&req)
+        .await
+}
+
+macro_rules! app {
+    ($factory:expr) => {
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new($factory))
+                .service(web::resource("/dashboard").to(handler)),
+        )
+        .await
+    };
+}
+
+fn visit() -> test::TestRequest {
+    test::TestRequest::get()
+        .uri("/dashboard")
+        .insert_header((X_INERTIA, "true"))
+}
+
+#[actix_web::test]
+async fn test_unchanged_pages_are_not_modified() {
+    let app = app!(ResponseFactory::new());
+
+    let resp = test::call_service(&app, visit().to_request()).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    let etag = resp.headers().get(http::header::ETAG).unwrap().clone();
+
+    let req = visit()
+        .insert_header((http::header::IF_NONE_MATCH, etag.clone()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::NOT_MODIFIED);
+    assert_eq!(resp.headers().get(http::header::ETAG).unwrap(), &etag);
+    assert!(test::read_body(resp).await.is_empty());
+}
+
+#[actix_web::test]
+async fn test_partial_reloads_have_their_own_etag() {
+    let app = app!(ResponseFactory::new());
+
+    let resp = test::call_service(&app, visit().to_request()).await;
+    let etag = resp.headers().get(http::header::ETAG).unwrap().clone();
+
+    let partial = || {
+        visit()
+            .insert_header((X_INERTIA_PARTIAL_COMPONENT, "Dashboard"))
+            .insert_header((X_INERTIA_PARTIAL_ONLY, "visits"))
+    };
+    let req = partial()
+        .insert_header((http::header::IF_NONE_MATCH, etag.clone()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    let partial_etag = resp.headers().get(http::header::ETAG).unwrap().clone();
+    assert_ne!(partial_etag, etag);
+
+    let req = partial()
+        .insert_header((
+            http::header::IF_NONE_MATCH,
+            format!("W/{}", partial_etag.to_str().unwrap()),
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::NOT_MODIFIED);
+}
+
+#[actix_web::test]
+async fn test_shared_props_and_versions_change_the_etag() {
+    let factory = ResponseFactory::new();
+    let resp = test::call_service(&app!(factory.clone()), visit().to_request()).await;
+    let etag = resp.headers().get(http::header::ETAG).unwrap().clone();
+
+    let mut versioned = factory.clone();
+    versioned.set_version(|| "2".to_string());
+    let req = visit()
+        .insert_header((X_INERTIA_VERSION, "2"))
+        .insert_header((http::header::IF_NONE_MATCH, etag.clone()))
+        .to_request();
+    let resp = test::call_service(&app!(versioned), req).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+
+    let app = test::init_service(
+        App::new()
+            .wrap(actix_inertia::InertiaLayer::new(factory).share(|_| json!({"user": "Ada"})))
+            .service(web::resource("/dashboard").to(handler)),
+    )
+    .await;
+    let req = visit()
+        .insert_header((http::header::IF_NONE_MATCH, etag))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_etags_can_be_turned_off() {
+    let mut factory = ResponseFactory::new();
+    factory.set_etags(false);
+    let app = app!(factory);
+
+    let req = visit()
+        .insert_header((http::header::IF_NONE_MATCH, "*"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    assert!(!resp.headers().contains_key(http::header::ETAG));
+}
+
+#[actix_web::test]
+async fn test_full_page_loads_have_no_etag() {
+    let app = app!(ResponseFactory::new());
+
+    let req = test::TestRequest::get().uri("/dashboard").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert!(!resp.headers().contains_key(http::header::ETAG));
+}
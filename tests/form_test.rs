@@ -0,0 +1,225 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::validation::ERRORS_COOKIE;
+use actix_inertia::{InertiaForm, MethodSpoofingMiddleware, X_INERTIA};
+use actix_web::{http, test, web, App, HttpResponse};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct UserForm {
+    name: String,
+    age: u32,
+}
+
+async fn update(form: InertiaForm<UserForm>) -> HttpResponse {
+    HttpResponse::Ok().body(format!("{} {}", form.name, form.age))
+}
+
+async fn method_name(req: actix_web::HttpRequest) -> HttpResponse {
+    HttpResponse::Ok().body(req.method().to_string())
+}
+
+const BOUNDARY: &str = "----inertia-boundary";
+
+fn multipart_body(fields: &[(&str, &str)]) -> String {
+    let mut body = String::new();
+    for (name, value) in fields {
+        body.push_str(&format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+        ));
+    }
+    body.push_str(&format!("--{BOUNDARY}--\r\n"));
+    body
+}
+
+#[actix_web::test]
+async fn test_method_is_spoofed_from_urlencoded_body() {
+    let app = test::init_service(
+        App::new().wrap(MethodSpoofingMiddleware::new()).service(
+            web::resource("/users/1")
+                .route(web::put().to(method_name))
+                .route(web::post().to(method_name)),
+        ),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/users/1")
+        .insert_header((
+            http::header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        ))
+        .set_payload("_method=PUT&name=Ada")
+        .to_request();
+    let body = test::call_and_read_body(&app, req).await;
+
+    assert_eq!(body, "PUT");
+}
+
+#[actix_web::test]
+async fn test_method_is_spoofed_from_multipart_body_and_body_is_kept() {
+    let app = test::init_service(
+        App::new()
+            .wrap(MethodSpoofingMiddleware::new())
+            .service(web::resource("/users/1").route(web::patch().to(update))),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/users/1")
+        .insert_header((
+            http::header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        ))
+        .set_payload(multipart_body(&[
+            ("_method", "PATCH"),
+            ("name", "Ada"),
+            ("age", "36"),
+        ]))
+        .to_request();
+    let body = test::call_and_read_body(&app, req).await;
+
+    assert_eq!(body, "Ada 36");
+}
+
+#[actix_web::test]
+async fn test_only_put_patch_and_delete_can_be_spoofed() {
+    let app = test::init_service(
+        App::new()
+            .wrap(MethodSpoofingMiddleware::new())
+            .service(web::resource("/users/1").to(method_name)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/users/1")
+        .insert_header((
+            http::header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        ))
+        .set_payload("_method=GET")
+        .to_request();
+    let body = test::call_and_read_body(&app, req).await;
+
+    assert_eq!(body, "POST");
+}
+
+async fn body_length(body: actix_web::web::Bytes) -> HttpResponse {
+    HttpResponse::Ok().body(body.len().to_string())
+}
+
+#[actix_web::test]
+async fn test_urlencoded_bodies_over_the_limit_are_rejected() {
+    let app = test::init_service(
+        App::new()
+            .wrap(MethodSpoofingMiddleware::new().limit(16))
+            .service(web::resource("/users/1").to(method_name)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/users/1")
+        .insert_header((
+            http::header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        ))
+        .set_payload(format!("_method=PUT&bio={}", "a".repeat(64)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[actix_web::test]
+async fn test_multipart_bodies_are_streamed_past_the_limit() {
+    let app = test::init_service(
+        App::new()
+            .wrap(MethodSpoofingMiddleware::new().limit(128))
+            .service(
+                web::resource("/users/1")
+                    .route(web::put().to(body_length))
+                    .route(web::post().to(method_name)),
+            ),
+    )
+    .await;
+    let multipart = |fields: &[(&str, &str)]| {
+        test::TestRequest::post()
+            .uri("/users/1")
+            .insert_header((
+                http::header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={BOUNDARY}"),
+            ))
+            .set_payload(multipart_body(fields))
+    };
+    let avatar = "a".repeat(4096);
+
+    let body = multipart_body(&[("_method", "PUT"), ("avatar", &avatar)]);
+    let req = multipart(&[("_method", "PUT"), ("avatar", &avatar)]).to_request();
+    let resp = test::call_and_read_body(&app, req).await;
+    assert_eq!(resp, body.len().to_string());
+
+    let req = multipart(&[("avatar", &avatar), ("_method", "PUT")]).to_request();
+    let resp = test::call_and_read_body(&app, req).await;
+    assert_eq!(resp, "POST");
+}
+
+#[actix_web::test]
+async fn test_inertia_form_accepts_json() {
+    let app = test::init_service(App::new().service(web::resource("/users").to(update))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/users")
+        .insert_header((X_INERTIA, "true"))
+        .set_json(serde_json::json!({"name": "Ada", "age": 36}))
+        .to_request();
+    let body = test::call_and_read_body(&app, req).await;
+
+    assert_eq!(body, "Ada 36");
+}
+
+#[actix_web::test]
+async fn test_inertia_form_redirects_back_with_errors() {
+    let app = test::init_service(App::new().service(web::resource("/users").to(update))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/users")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((http::header::REFERER, "/users/create"))
+        .set_json(serde_json::json!({"age": "old"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::SEE_OTHER);
+    assert_eq!(
+        resp.headers().get(http::header::LOCATION).unwrap(),
+        "/users/create"
+    );
+    let cookie = resp
+        .response()
+        .cookies()
+        .find(|c| c.name() == ERRORS_COOKIE)
+        .expect("errors are flashed");
+    let errors: serde_json::Value = serde_json::from_str(cookie.value()).unwrap();
+    assert!(errors["age"].is_string());
+}
+
+#[actix_web::test]
+async fn test_inertia_form_reports_missing_fields() {
+    let app = test::init_service(App::new().service(web::resource("/users").to(update))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/users")
+        .insert_header((X_INERTIA, "true"))
+        .set_json(serde_json::json!({"age": 36}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::SEE_OTHER);
+    let cookie = resp
+        .response()
+        .cookies()
+        .find(|c| c.name() == ERRORS_COOKIE)
+        .expect("errors are flashed");
+    let errors: serde_json::Value = serde_json::from_str(cookie.value()).unwrap();
+    assert_eq!(errors["name"], "The name field is required.");
+}
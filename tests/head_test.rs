@@ -0,0 +1,124 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{inertia_responder::InertiaResponder, Head, ResponseFactory, X_INERTIA};
+use actix_web::{test, web, App, HttpRequest, Responder};
+use serde_json::{json, Value};
+use std::fs;
+
+async fn post(req: HttpRequest) -> impl Responder {
+    InertiaResponder::new("Posts/Show", json!({ "id": 1 }))
+        .head(
+            Head::new()
+                .title("Hello & welcome")
+                .meta("description", "A \"quoted\" summary")
+                .og("image", "https://example.com/1.png")
+                .canonical("https://example.com/posts/1")
+                .json_ld(json!({ "@type": "BlogPosting", "headline": "</script>" })),
+        )
+        .respond_to(&req)
+        .await
+}
+
+fn factory() -> ResponseFactory {
+    let path = std::env::temp_dir().join("actix-inertia-head.html");
+    fs::write(
+        &path,
+        "<head>{{HEAD}}</head><div data-page=\"{{DATA_PAGE}}\"></div>",
+    )
+    .unwrap();
+    let mut factory = ResponseFactory::new();
+    factory.set_root_view(&path.to_string_lossy());
+    factory
+}
+
+#[actix_web::test]
+async fn test_head_is_rendered_into_the_root_view() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(factory()))
+            .route("/posts/1", web::get().to(post)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/posts/1").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8_lossy(&body);
+
+    assert!(body.contains("<head><title>Hello &amp; welcome</title>"));
+    assert!(body.contains("<meta name=\"description\" content=\"A &quot;quoted&quot; summary\">"));
+    assert!(body.contains("<meta property=\"og:image\" content=\"https://example.com/1.png\">"));
+    assert!(body.contains("<link rel=\"canonical\" href=\"https://example.com/posts/1\">"));
+    assert!(body.contains("\"headline\":\"<\\/script>\""));
+}
+
+#[actix_web::test]
+async fn test_placeholders_in_head_text_are_not_replaced() {
+    let app = test::init_service(App::new().app_data(web::Data::new(factory())).route(
+        "/posts/2",
+        web::get().to(|req: HttpRequest| async move {
+            InertiaResponder::new("Posts/Show", json!({ "secret": "s3cr3t" }))
+                .head(Head::new().title("{{DATA_PAGE}} {{HEAD}}"))
+                .respond_to(&req)
+                .await
+        }),
+    ))
+    .await;
+
+    let req = test::TestRequest::get().uri("/posts/2").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8_lossy(&body);
+
+    assert!(body.contains("<title>{{DATA_PAGE}} {{HEAD}}</title>"));
+    assert_eq!(body.matches("s3cr3t").count(), 1);
+}
+
+#[actix_web::test]
+async fn test_head_is_not_part_of_the_page_object() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(factory()))
+            .route("/posts/1", web::get().to(post)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/posts/1")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+
+    assert_eq!(body["props"], json!({ "id": 1 }));
+    assert!(body.get("head").is_none());
+}
+
+#[actix_web::test]
+async fn test_later_meta_tags_replace_earlier_ones() {
+    let head = Head::new()
+        .meta("description", "first")
+        .meta("description", "second");
+
+    assert_eq!(
+        head.render(),
+        "<meta name=\"description\" content=\"second\">"
+    );
+}
+
+#[actix_web::test]
+async fn test_ssr_head_replaces_duplicate_tags() {
+    let head = Head::new()
+        .title("Server title")
+        .meta("description", "Server description")
+        .og("type", "article");
+    let ssr_head = vec![
+        "<title inertia>SSR title</title>".to_string(),
+        "<meta name=\"description\" content=\"SSR description\" inertia>".to_string(),
+    ];
+
+    let merged = head.merge(&ssr_head);
+
+    assert!(!merged.contains("Server title"));
+    assert!(!merged.contains("Server description"));
+    assert!(merged.contains("SSR title"));
+    assert!(merged.contains("SSR description"));
+    assert!(merged.contains("og:type"));
+}
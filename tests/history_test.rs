@@ -0,0 +1,86 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, EncryptHistoryMiddleware, ResponseFactory, X_INERTIA,
+};
+use actix_web::{http, test, web, App, HttpRequest};
+use serde_json::Value;
+
+async fn handler(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("ComponentName", serde_json::json!({}))
+        .respond_to(&req)
+        .await
+}
+
+async fn logout(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("ComponentName", serde_json::json!({}))
+        .clear_history()
+        .respond_to(&req)
+        .await
+}
+
+async fn public(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("ComponentName", serde_json::json!({}))
+        .encrypt_history(false)
+        .respond_to(&req)
+        .await
+}
+
+async fn page(app_factory: ResponseFactory, uri: &str) -> Value {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_factory))
+            .service(web::resource("/foo").to(handler))
+            .service(web::resource("/logout").to(logout))
+            .service(web::resource("/public").to(public))
+            .service(
+                web::scope("/account")
+                    .wrap(EncryptHistoryMiddleware)
+                    .service(web::resource("/settings").to(handler)),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(uri)
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+
+    let body_bytes = test::read_body(resp).await;
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+#[actix_web::test]
+async fn test_history_is_not_encrypted_by_default() {
+    let body = page(ResponseFactory::new(), "/foo").await;
+
+    assert_eq!(body["encryptHistory"], false);
+    assert_eq!(body["clearHistory"], false);
+}
+
+#[actix_web::test]
+async fn test_factory_default_encrypts_history() {
+    let mut factory = ResponseFactory::new();
+    factory.set_encrypt_history(true);
+
+    assert_eq!(page(factory.clone(), "/foo").await["encryptHistory"], true);
+    assert_eq!(page(factory, "/public").await["encryptHistory"], false);
+}
+
+#[actix_web::test]
+async fn test_middleware_encrypts_history_for_scope() {
+    let body = page(ResponseFactory::new(), "/account/settings").await;
+    assert_eq!(body["encryptHistory"], true);
+
+    let body = page(ResponseFactory::new(), "/foo").await;
+    assert_eq!(body["encryptHistory"], false);
+}
+
+#[actix_web::test]
+async fn test_response_can_clear_history() {
+    let body = page(ResponseFactory::new(), "/logout").await;
+
+    assert_eq!(body["clearHistory"], true);
+}
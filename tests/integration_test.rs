@@ -1,3 +1,5 @@
+#![cfg(feature = "actix")]
+
 use actix_inertia::{
     example_handler, ResponseFactory, VersionMiddleware, X_INERTIA, X_INERTIA_VERSION,
     X_INERTIA_PARTIAL_COMPONENT, X_INERTIA_PARTIAL_ONLY, X_INERTIA_PARTIAL_EXCEPT,
@@ -0,0 +1,178 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, ErrorPages, InertiaLayer, ResponseFactory, X_INERTIA,
+    X_INERTIA_VERSION,
+};
+use actix_web::{http, test, web, App, HttpRequest, HttpResponse, Responder};
+use serde_json::{json, Value};
+
+async fn handler(req: HttpRequest) -> impl Responder {
+    InertiaResponder::new("Dashboard", json!({ "title": "Home" }))
+        .respond_to(&req)
+        .await
+}
+
+async fn version(factory: web::Data<ResponseFactory>) -> impl Responder {
+    HttpResponse::Ok().body(factory.get_version())
+}
+
+async fn update() -> impl Responder {
+    HttpResponse::Found()
+        .append_header((http::header::LOCATION, "/"))
+        .finish()
+}
+
+fn factory() -> ResponseFactory {
+    let mut factory = ResponseFactory::new();
+    factory.set_version(|| "1".to_string());
+    factory
+}
+
+#[actix_web::test]
+async fn test_layer_registers_the_factory() {
+    let app = test::init_service(
+        App::new()
+            .wrap(InertiaLayer::new(factory()))
+            .route("/version", web::get().to(version)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/version").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+
+    assert_eq!(body, "1");
+}
+
+#[actix_web::test]
+async fn test_layer_checks_the_asset_version() {
+    let app = test::init_service(
+        App::new()
+            .wrap(InertiaLayer::new(factory()))
+            .route("/", web::get().to(handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "0"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "1"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["version"], "1");
+}
+
+#[actix_web::test]
+async fn test_version_check_can_be_turned_off() {
+    let app = test::init_service(
+        App::new()
+            .wrap(InertiaLayer::new(factory()).version_check(false))
+            .route("/", web::get().to(handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "0"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_redirects_after_updates_become_see_other() {
+    let app = test::init_service(
+        App::new()
+            .wrap(InertiaLayer::new(factory()))
+            .route("/users/1", web::put().to(update))
+            .route("/users", web::post().to(update)),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/users/1")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::SEE_OTHER);
+
+    let req = test::TestRequest::post()
+        .uri("/users")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::FOUND);
+}
+
+#[actix_web::test]
+async fn test_see_other_redirects_can_be_turned_off() {
+    let app = test::init_service(
+        App::new()
+            .wrap(InertiaLayer::new(factory()).see_other_redirects(false))
+            .route("/users/1", web::put().to(update)),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/users/1")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::FOUND);
+}
+
+#[actix_web::test]
+async fn test_shared_props_are_added_to_every_page() {
+    let app = test::init_service(
+        App::new()
+            .wrap(
+                InertiaLayer::new(factory())
+                    .share(|req| json!({ "path": req.path(), "title": "Shared" })),
+            )
+            .route("/", web::get().to(handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "1"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+
+    assert_eq!(body["props"]["path"], "/");
+    assert_eq!(body["props"]["title"], "Home");
+}
+
+#[actix_web::test]
+async fn test_layer_renders_error_pages() {
+    let app = test::init_service(
+        App::new()
+            .wrap(InertiaLayer::new(factory()).error_pages(ErrorPages::new("Error")))
+            .route("/", web::get().to(handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/missing")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "1"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["component"], "Error");
+    assert_eq!(body["version"], "1");
+}
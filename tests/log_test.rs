@@ -0,0 +1,57 @@
+#![cfg(not(feature = "tracing"))]
+
+use actix_inertia::{Inertia, InertiaRequest, Oversized, ResponseFactory, SizeLimit};
+use log::{Level, Log, Metadata, Record};
+use serde_json::json;
+use std::sync::Mutex;
+
+/// Every record's level, target and message.
+struct Records(Mutex<Vec<(Level, String, String)>>);
+
+impl Log for Records {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.0.lock().unwrap().push((
+            record.level(),
+            record.target().to_string(),
+            record.args().to_string(),
+        ));
+    }
+
+    fn flush(&self) {}
+}
+
+static RECORDS: Records = Records(Mutex::new(Vec::new()));
+
+#[test]
+fn test_problems_are_logged_without_tracing() {
+    log::set_logger(&RECORDS).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let mut factory = ResponseFactory::new();
+    factory.set_dev_mode(false);
+    factory.set_size_limit(SizeLimit::new(16).on_oversized(Oversized::Warn));
+    let (parts, _) = http::Request::get("/users")
+        .header("X-Inertia", "true")
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    Inertia::new(
+        "Users".to_string(),
+        json!({ "users": vec!["ada@example.com"; 10] }),
+        "/users".to_string(),
+    )
+    .render_page(&InertiaRequest::from_parts(&parts), Some(&factory))
+    .unwrap();
+
+    let records = RECORDS.0.lock().unwrap();
+    assert_eq!(records.len(), 1);
+    let (level, target, message) = &records[0];
+    assert_eq!(*level, Level::Warn);
+    assert_eq!(target, "actix_inertia");
+    assert!(message.contains("Users"), "{message}");
+}
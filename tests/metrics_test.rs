@@ -0,0 +1,57 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, InertiaLayer, ResponseFactory, VersionMiddleware,
+    X_INERTIA, X_INERTIA_VERSION,
+};
+use actix_web::{http, test, web, App, HttpRequest};
+use serde_json::json;
+
+async fn handler(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("Dashboard", json!({}))
+        .respond_to(&req)
+        .await
+}
+
+fn stale_visit(uri: &str) -> test::TestRequest {
+    test::TestRequest::get()
+        .uri(uri)
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "old"))
+}
+
+#[actix_web::test]
+async fn test_version_conflicts_are_counted() {
+    let mut factory = ResponseFactory::new();
+    factory.set_version(|| "new".to_string());
+    let app = test::init_service(
+        App::new()
+            .wrap(InertiaLayer::new(factory.clone()))
+            .service(web::resource("/dashboard").to(handler)),
+    )
+    .await;
+
+    let resp = test::call_service(&app, stale_visit("/dashboard").to_request()).await;
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+    let resp = test::call_service(&app, stale_visit("/dashboard").to_request()).await;
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+
+    assert_eq!(factory.metrics().version_conflicts(), 2);
+}
+
+#[actix_web::test]
+async fn test_version_middleware_counts_with_a_registered_factory() {
+    let factory = ResponseFactory::new();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(factory.clone()))
+            .wrap(VersionMiddleware::new("new".to_string()))
+            .service(web::resource("/dashboard").to(handler)),
+    )
+    .await;
+
+    let resp = test::call_service(&app, stale_visit("/dashboard").to_request()).await;
+
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+    assert_eq!(factory.metrics().version_conflicts(), 1);
+}
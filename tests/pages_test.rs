@@ -0,0 +1,133 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, MissingComponent, PagesDirectory, ResponseFactory,
+    X_INERTIA,
+};
+use actix_web::{http, test, web, App, HttpRequest};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+fn pages_dir(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir = env::temp_dir().join(format!("{name}_{unique}"));
+    fs::create_dir_all(dir.join("Users")).unwrap();
+    fs::write(dir.join("Hello.vue"), "<template></template>").unwrap();
+    fs::write(dir.join("Users/Show.tsx"), "export default () => null").unwrap();
+    dir
+}
+
+async fn typo(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("Helo", serde_json::json!({}))
+        .respond_to(&req)
+        .await
+}
+
+async fn hello(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("Hello", serde_json::json!({}))
+        .respond_to(&req)
+        .await
+}
+
+#[actix_web::test]
+async fn test_components_resolve_against_the_pages_directory() {
+    let dir = pages_dir("pages_resolve");
+    let pages = PagesDirectory::new(&dir);
+
+    assert_eq!(pages.resolve("Hello"), Some(dir.join("Hello.vue")));
+    assert!(pages.exists("Users/Show"));
+    assert!(!pages.exists("Users/Index"));
+    assert!(!pages.exists("../Hello"));
+    assert!(!PagesDirectory::new(&dir)
+        .extensions(&[".svelte"])
+        .exists("Hello"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[actix_web::test]
+async fn test_missing_component_fails_when_configured() {
+    let dir = pages_dir("pages_fail");
+    let mut factory = ResponseFactory::new();
+    factory.set_dev_mode(true);
+    factory.set_pages_directory(PagesDirectory::new(&dir).on_missing(MissingComponent::Fail));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(factory))
+            .service(web::resource("/typo").to(typo))
+            .service(web::resource("/hello").to(hello)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/typo")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+
+    let req = test::TestRequest::get()
+        .uri("/hello")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[actix_web::test]
+async fn test_missing_component_only_warns_by_default() {
+    let dir = pages_dir("pages_warn");
+    let mut factory = ResponseFactory::new();
+    factory.set_dev_mode(true);
+    factory.set_pages_directory(PagesDirectory::new(&dir));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(factory))
+            .service(web::resource("/typo").to(typo)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/typo")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(feature = "testing")]
+#[actix_web::test]
+async fn test_component_existence_can_be_asserted() {
+    use actix_inertia::testing::{assert_inertia, InertiaTestRequest};
+
+    let dir = pages_dir("pages_assert");
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .service(web::resource("/hello").to(hello)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/hello")
+        .inertia("")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_inertia(resp)
+        .await
+        .component("Hello")
+        .component_exists(&PagesDirectory::new(&dir));
+
+    fs::remove_dir_all(dir).unwrap();
+}
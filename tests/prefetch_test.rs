@@ -0,0 +1,156 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, is_prefetch, CacheControl, ResponseFactory, PURPOSE,
+    X_INERTIA,
+};
+use actix_web::{http, test, web, App, HttpRequest};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+async fn handler(req: HttpRequest, views: web::Data<AtomicUsize>) -> impl actix_web::Responder {
+    if !is_prefetch(&req) {
+        views.fetch_add(1, Ordering::SeqCst);
+    }
+    InertiaResponder::new("Posts/Show", json!({"title": "Hello"}))
+        .respond_to(&req)
+        .await
+}
+
+macro_rules! app {
+    ($factory:expr, $views:expr) => {
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new($factory))
+                .app_data(web::Data::from($views.clone()))
+                .service(web::resource("/posts/1").to(handler)),
+        )
+        .await
+    };
+}
+
+#[actix_web::test]
+async fn test_prefetch_responses_get_no_cache_hints_by_default() {
+    let views = Arc::new(AtomicUsize::new(0));
+    let app = app!(ResponseFactory::new(), views);
+
+    let req = test::TestRequest::get()
+        .uri("/posts/1")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((PURPOSE, "prefetch"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    assert!(!resp.headers().contains_key(http::header::CACHE_CONTROL));
+    assert!(!resp
+        .headers()
+        .get(http::header::VARY)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .contains(PURPOSE));
+    assert_eq!(views.load(Ordering::SeqCst), 0);
+}
+
+#[actix_web::test]
+async fn test_prefetch_responses_get_cache_hints() {
+    let mut factory = ResponseFactory::new();
+    factory.set_prefetch_cache(Some(
+        CacheControl::private().max_age(Duration::from_secs(30)),
+    ));
+    let views = Arc::new(AtomicUsize::new(0));
+    let app = app!(factory, views);
+
+    let req = test::TestRequest::get()
+        .uri("/posts/1")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((PURPOSE, "prefetch"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    assert_eq!(
+        resp.headers().get(http::header::CACHE_CONTROL).unwrap(),
+        "private, max-age=30"
+    );
+    assert!(resp
+        .headers()
+        .get(http::header::VARY)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .ends_with("Purpose, Sec-Purpose"));
+    assert_eq!(views.load(Ordering::SeqCst), 0);
+}
+
+#[actix_web::test]
+async fn test_normal_visits_get_no_cache_hints() {
+    let views = Arc::new(AtomicUsize::new(0));
+    let app = app!(ResponseFactory::new(), views);
+
+    let req = test::TestRequest::get()
+        .uri("/posts/1")
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert!(!resp.headers().contains_key(http::header::CACHE_CONTROL));
+    assert_eq!(views.load(Ordering::SeqCst), 1);
+}
+
+#[actix_web::test]
+async fn test_prefetch_cache_is_configurable() {
+    let mut factory = ResponseFactory::new();
+    factory.set_prefetch_cache(Some(
+        CacheControl::private()
+            .max_age(Duration::from_secs(10))
+            .stale_while_revalidate(Duration::from_secs(60)),
+    ));
+    let views = Arc::new(AtomicUsize::new(0));
+    let app = app!(factory, views);
+
+    let req = test::TestRequest::get()
+        .uri("/posts/1")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header(("Sec-Purpose", "prefetch;prerender"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(
+        resp.headers().get(http::header::CACHE_CONTROL).unwrap(),
+        "private, max-age=10, stale-while-revalidate=60"
+    );
+}
+
+#[actix_web::test]
+async fn test_prefetch_keeps_flashed_errors() {
+    let views = Arc::new(AtomicUsize::new(0));
+    let app = app!(ResponseFactory::new(), views);
+
+    let req = test::TestRequest::get()
+        .uri("/posts/1")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((PURPOSE, "prefetch"))
+        .cookie(actix_web::cookie::Cookie::new(
+            "inertia_errors",
+            r#"{"title":"Required"}"#,
+        ))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert!(resp.response().cookies().next().is_none());
+}
+
+#[actix_web::test]
+async fn test_cache_control_values() {
+    assert_eq!(CacheControl::no_store().to_string(), "no-store");
+    assert_eq!(
+        CacheControl::public()
+            .max_age(Duration::from_secs(60))
+            .to_string(),
+        "public, max-age=60"
+    );
+}
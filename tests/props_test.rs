@@ -0,0 +1,151 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, ResponseFactory, X_INERTIA, X_INERTIA_PARTIAL_COMPONENT,
+    X_INERTIA_PARTIAL_ONLY, X_INERTIA_RESET,
+};
+use actix_web::{http, test, web, App, HttpRequest};
+use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+async fn dashboard(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new(
+        "Dashboard",
+        serde_json::json!({
+            "user": "Ada",
+            "stats": {"visits": 10},
+            "flash": "Saved",
+            "teams": ["core"],
+            "projects": ["inertia"],
+            "comments": [1, 2, 3],
+        }),
+    )
+    .lazy("stats")
+    .always("flash")
+    .defer("teams", "sidebar")
+    .defer("projects", "sidebar")
+    .merge("comments")
+    .respond_to(&req)
+    .await
+}
+
+async fn visit(headers: &[(&'static str, &'static str)]) -> Value {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .service(web::resource("/dashboard").to(dashboard)),
+    )
+    .await;
+
+    let mut req = test::TestRequest::get()
+        .uri("/dashboard")
+        .insert_header((X_INERTIA, "true"));
+    for header in headers {
+        req = req.insert_header(*header);
+    }
+    let resp = test::call_service(&app, req.to_request()).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+
+    serde_json::from_slice(&test::read_body(resp).await).unwrap()
+}
+
+#[actix_web::test]
+async fn test_first_visit_leaves_out_lazy_and_deferred_props() {
+    let body = visit(&[]).await;
+
+    assert_eq!(body["props"]["user"], "Ada");
+    assert!(body["props"].get("stats").is_none());
+    assert!(body["props"].get("teams").is_none());
+    assert_eq!(
+        body["deferredProps"]["sidebar"],
+        serde_json::json!(["teams", "projects"])
+    );
+    assert_eq!(body["mergeProps"], serde_json::json!(["comments"]));
+}
+
+#[actix_web::test]
+async fn test_partial_reload_sends_requested_and_always_props() {
+    let body = visit(&[
+        (X_INERTIA_PARTIAL_COMPONENT, "Dashboard"),
+        (X_INERTIA_PARTIAL_ONLY, "stats,teams"),
+    ])
+    .await;
+
+    assert_eq!(body["props"]["stats"]["visits"], 10);
+    assert_eq!(body["props"]["teams"], serde_json::json!(["core"]));
+    assert_eq!(body["props"]["flash"], "Saved");
+    assert!(body["props"].get("user").is_none());
+    assert!(body.get("deferredProps").is_none());
+    assert!(body.get("mergeProps").is_none());
+}
+
+#[actix_web::test]
+async fn test_reset_props_are_not_merged() {
+    let body = visit(&[
+        (X_INERTIA_PARTIAL_COMPONENT, "Dashboard"),
+        (X_INERTIA_PARTIAL_ONLY, "comments"),
+        (X_INERTIA_RESET, "comments"),
+    ])
+    .await;
+
+    assert_eq!(body["props"]["comments"], serde_json::json!([1, 2, 3]));
+    assert!(body.get("mergeProps").is_none());
+}
+
+static STATS_CALLS: AtomicUsize = AtomicUsize::new(0);
+static TEAMS_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+async fn reports(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("Reports", serde_json::json!({"user": "Ada"}))
+        .lazy_with("stats", || {
+            STATS_CALLS.fetch_add(1, Ordering::SeqCst);
+            serde_json::json!({"visits": 10})
+        })
+        .defer_with("teams", "sidebar", || {
+            TEAMS_CALLS.fetch_add(1, Ordering::SeqCst);
+            vec!["core"]
+        })
+        .respond_to(&req)
+        .await
+}
+
+#[actix_web::test]
+async fn test_prop_closures_only_run_when_the_prop_is_sent() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .service(web::resource("/reports").to(reports)),
+    )
+    .await;
+    let visit = |only: Option<&'static str>| {
+        let mut req = test::TestRequest::get()
+            .uri("/reports")
+            .insert_header((X_INERTIA, "true"));
+        if let Some(only) = only {
+            req = req
+                .insert_header((X_INERTIA_PARTIAL_COMPONENT, "Reports"))
+                .insert_header((X_INERTIA_PARTIAL_ONLY, only));
+        }
+        req.to_request()
+    };
+
+    let body: Value = test::call_and_read_body_json(&app, visit(None)).await;
+    assert!(body["props"].get("stats").is_none());
+    assert_eq!(
+        body["deferredProps"]["sidebar"],
+        serde_json::json!(["teams"])
+    );
+    assert_eq!(STATS_CALLS.load(Ordering::SeqCst), 0);
+    assert_eq!(TEAMS_CALLS.load(Ordering::SeqCst), 0);
+
+    let body: Value = test::call_and_read_body_json(&app, visit(Some("stats"))).await;
+    assert_eq!(body["props"]["stats"]["visits"], 10);
+    assert_eq!(STATS_CALLS.load(Ordering::SeqCst), 1);
+    assert_eq!(TEAMS_CALLS.load(Ordering::SeqCst), 0);
+
+    let body: Value = test::call_and_read_body_json(&app, visit(Some("teams"))).await;
+    assert_eq!(body["props"]["teams"], serde_json::json!(["core"]));
+    assert!(body["props"].get("stats").is_none());
+    assert_eq!(STATS_CALLS.load(Ordering::SeqCst), 1);
+    assert_eq!(TEAMS_CALLS.load(Ordering::SeqCst), 1);
+}
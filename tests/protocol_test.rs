@@ -0,0 +1,155 @@
+use actix_inertia::{
+    etag, CacheControl, Inertia, InertiaRequest, PropOptions, Rendered, ResponseFactory,
+};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+fn request(builder: http::request::Builder) -> InertiaRequest {
+    let (parts, _) = builder.body(()).unwrap().into_parts();
+    InertiaRequest::from_parts(&parts)
+}
+
+fn page(rendered: Rendered) -> Value {
+    match rendered {
+        Rendered::Json(page) | Rendered::NoRootView(page) => serde_json::from_str(&page).unwrap(),
+        other => panic!("expected a page object, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_request_headers_are_read_from_parts() {
+    let request = request(
+        http::Request::get("/users?page=2")
+            .header("X-Inertia", "true")
+            .header("X-Inertia-Version", "1")
+            .header("X-Inertia-Partial-Component", "Users")
+            .header("X-Inertia-Partial-Data", "users, filters")
+            .header("X-Inertia-Reset", "users")
+            .header(
+                "Cookie",
+                "theme=dark; inertia_errors={\"name\":\"Required\"}",
+            ),
+    );
+
+    assert!(request.inertia);
+    assert_eq!(request.url, "/users?page=2");
+    assert_eq!(request.version.as_deref(), Some("1"));
+    assert_eq!(request.partial_component.as_deref(), Some("Users"));
+    assert_eq!(
+        request.partial_only,
+        Some(vec!["users".to_string(), "filters".to_string()])
+    );
+    assert_eq!(request.reset, vec!["users".to_string()]);
+    assert_eq!(request.errors, Some(json!({ "name": "Required" })));
+}
+
+#[test]
+fn test_version_conflicts_only_apply_to_inertia_gets() {
+    let stale = request(
+        http::Request::get("/")
+            .header("X-Inertia", "true")
+            .header("X-Inertia-Version", "old"),
+    );
+    assert!(stale.version_conflict(|version| version == Some("new")));
+    assert!(!stale.version_conflict(|version| version == Some("old")));
+
+    let post = request(
+        http::Request::post("/")
+            .header("X-Inertia", "true")
+            .header("X-Inertia-Version", "old"),
+    );
+    assert!(!post.version_conflict(|version| version == Some("new")));
+
+    let full_load = request(http::Request::get("/"));
+    assert!(!full_load.version_conflict(|version| version == Some("new")));
+}
+
+#[test]
+fn test_partial_reloads_filter_props() {
+    let request = request(
+        http::Request::get("/dashboard")
+            .header("X-Inertia", "true")
+            .header("X-Inertia-Partial-Component", "Dashboard")
+            .header("X-Inertia-Partial-Data", "stats"),
+    );
+
+    let rendered = Inertia::new(
+        "Dashboard".to_string(),
+        json!({ "stats": 1, "users": 2, "auth": 3 }),
+        "/dashboard".to_string(),
+    )
+    .prop_options(PropOptions::new().always("auth"))
+    .render_page(&request, None)
+    .unwrap();
+
+    assert_eq!(page(rendered)["props"], json!({ "stats": 1, "auth": 3 }));
+}
+
+#[test]
+fn test_first_visits_list_deferred_props() {
+    let request = request(
+        http::Request::get("/dashboard")
+            .header("X-Inertia", "true")
+            .header("X-Inertia-Version", "7"),
+    );
+    let mut factory = ResponseFactory::new();
+    factory.set_version(|| "7".to_string());
+
+    let rendered = Inertia::new(
+        "Dashboard".to_string(),
+        json!({ "stats": 1, "chart": 2 }),
+        "/dashboard".to_string(),
+    )
+    .defer("chart", "charts")
+    .render_page(&request, Some(&factory))
+    .unwrap();
+
+    let page = page(rendered);
+    assert_eq!(page["props"], json!({ "stats": 1 }));
+    assert_eq!(page["deferredProps"], json!({ "charts": ["chart"] }));
+    assert_eq!(page["version"], "7");
+}
+
+#[test]
+fn test_full_page_loads_without_a_root_view_send_the_page_object() {
+    let request = request(http::Request::get("/"));
+
+    let rendered = Inertia::new("Home".to_string(), json!({}), "/".to_string())
+        .render_page(&request, Some(&ResponseFactory::new()))
+        .unwrap();
+
+    assert_eq!(page(rendered)["component"], "Home");
+}
+
+#[test]
+fn test_etags_are_stable() {
+    assert_eq!(etag("{}"), "\"08f44b07b5901a25\"");
+    assert_ne!(etag("{}"), etag("{ }"));
+}
+
+#[test]
+fn test_prefetch_requests_are_detected() {
+    let prefetch = request(http::Request::get("/").header("Purpose", "prefetch"));
+    let visit = request(http::Request::get("/").header("X-Inertia", "true"));
+
+    assert!(prefetch.prefetch);
+    assert!(!visit.prefetch);
+    let mut factory = ResponseFactory::new();
+    factory.set_prefetch_cache(Some(
+        CacheControl::private().max_age(Duration::from_secs(30)),
+    ));
+    assert_eq!(
+        Rendered::Json("{}".to_string()).headers(&prefetch, Some(&factory)),
+        vec![
+            ("X-Inertia", "true".to_string()),
+            ("ETag", etag("{}")),
+            (
+                "Vary",
+                "X-Inertia, X-Inertia-Partial-Component, X-Inertia-Partial-Data, \
+                 X-Inertia-Partial-Except, X-Inertia-Reset, Purpose, Sec-Purpose"
+                    .to_string()
+            ),
+            ("Cache-Control", "private, max-age=30".to_string()),
+        ]
+    );
+}
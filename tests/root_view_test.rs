@@ -0,0 +1,220 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, InertiaLayer, ResponseFactory, RootView,
+    RootViewMiddleware, VersionMiddleware, X_INERTIA, X_INERTIA_LOCATION, X_INERTIA_VERSION,
+};
+use actix_web::{http, test, web, App, HttpRequest, Responder};
+use serde_json::{json, Value};
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+async fn dashboard(req: HttpRequest) -> impl Responder {
+    InertiaResponder::new("Dashboard", json!({}))
+        .respond_to(&req)
+        .await
+}
+
+async fn admin_login(req: HttpRequest) -> impl Responder {
+    InertiaResponder::new("Login", json!({}))
+        .root_view("admin")
+        .respond_to(&req)
+        .await
+}
+
+fn view(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(name);
+    fs::write(&path, contents).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+fn factory() -> ResponseFactory {
+    let mut factory = ResponseFactory::new();
+    factory.set_root_view(&view(
+        "actix-inertia-public.html",
+        "<main>public</main><div data-page=\"{{DATA_PAGE}}\"></div>{{VITE}}",
+    ));
+    factory.set_version(|| "public-1".to_string());
+    factory.add_root_view(
+        "admin",
+        RootView::new(view(
+            "actix-inertia-admin.html",
+            "<main>admin</main><div data-page=\"{{DATA_PAGE}}\"></div>{{VITE}}",
+        ))
+        .version("admin-1")
+        .vite_entry("/resources/js/admin.ts"),
+    );
+    factory
+}
+
+macro_rules! app {
+    () => {
+        test::init_service(
+            App::new()
+                .wrap(InertiaLayer::new(factory()))
+                .route("/", web::get().to(dashboard))
+                .route("/admin-login", web::get().to(admin_login))
+                .service(
+                    web::scope("/admin")
+                        .wrap(RootViewMiddleware::new("admin"))
+                        .route("", web::get().to(dashboard)),
+                ),
+        )
+        .await
+    };
+}
+
+#[actix_web::test]
+async fn test_scopes_render_their_own_root_view() {
+    let app = app!();
+
+    let req = test::TestRequest::get().uri("/").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8_lossy(&body);
+    assert!(body.contains("<main>public</main>"));
+    assert!(!body.contains("<script"));
+
+    let req = test::TestRequest::get().uri("/admin").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8_lossy(&body);
+    assert!(body.contains("<main>admin</main>"));
+    assert!(body.contains("<script type=\"module\" src=\"/resources/js/admin.ts\"></script>"));
+    assert!(body.contains("admin-1"));
+}
+
+#[actix_web::test]
+async fn test_responses_can_pick_a_root_view() {
+    let app = app!();
+
+    let req = test::TestRequest::get().uri("/admin-login").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+
+    assert!(String::from_utf8_lossy(&body).contains("<main>admin</main>"));
+}
+
+#[actix_web::test]
+async fn test_root_views_have_their_own_version() {
+    let app = app!();
+
+    let req = test::TestRequest::get()
+        .uri("/admin")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "admin-1"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["version"], "admin-1");
+
+    let req = test::TestRequest::get()
+        .uri("/admin")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "public-1"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "public-1"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["version"], "public-1");
+}
+
+#[actix_web::test]
+async fn test_unknown_root_views_are_errors() {
+    let app = test::init_service(
+        App::new().app_data(web::Data::new(factory())).service(
+            web::scope("/missing")
+                .wrap(RootViewMiddleware::new("missing"))
+                .route("", web::get().to(dashboard)),
+        ),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/missing").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+static ADMIN_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+async fn counted_dashboard(req: HttpRequest) -> impl Responder {
+    ADMIN_CALLS.fetch_add(1, Ordering::SeqCst);
+    dashboard(req).await
+}
+
+#[actix_web::test]
+async fn test_scope_versions_are_checked_before_the_handler_runs() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(factory()))
+            .wrap(VersionMiddleware::new("public-1".to_string()))
+            .route("/", web::get().to(dashboard))
+            .service(
+                web::scope("/admin")
+                    .wrap(RootViewMiddleware::new("admin"))
+                    .route("", web::get().to(counted_dashboard)),
+            ),
+    )
+    .await;
+    let stale = |uri: &str| {
+        test::TestRequest::get()
+            .uri(uri)
+            .insert_header((X_INERTIA, "true"))
+            .insert_header((X_INERTIA_VERSION, "public-1"))
+            .to_request()
+    };
+
+    let resp = test::call_service(&app, stale("/admin?tab=users")).await;
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+    assert_eq!(
+        resp.headers().get(X_INERTIA_LOCATION).unwrap(),
+        "/admin?tab=users"
+    );
+    assert_eq!(ADMIN_CALLS.load(Ordering::SeqCst), 0);
+
+    let req = test::TestRequest::get()
+        .uri("/admin")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "admin-1"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["version"], "admin-1");
+    assert_eq!(ADMIN_CALLS.load(Ordering::SeqCst), 1);
+
+    let req = test::TestRequest::get()
+        .uri("/?tab=users")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "old"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+    assert_eq!(
+        resp.headers().get(X_INERTIA_LOCATION).unwrap(),
+        "/?tab=users"
+    );
+}
+
+#[actix_web::test]
+async fn test_other_root_view_versions_conflict() {
+    let app = app!();
+
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "admin-1"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+    assert_eq!(resp.headers().get(X_INERTIA_LOCATION).unwrap(), "/");
+
+    let req = test::TestRequest::get()
+        .uri("/admin")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_VERSION, "public-1"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::{
+    inertia_responder::InertiaResponder, Oversized, ResponseFactory, SizeLimit, X_INERTIA,
+    X_INERTIA_DEBUG_PROP_SIZES,
+};
+use actix_web::{http, test, web, App, HttpRequest};
+use serde_json::json;
+
+async fn handler(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new(
+        "Users",
+        json!({
+            "title": "Users",
+            "users": vec!["ada@example.com"; 100],
+            "roles": ["admin", "editor"],
+        }),
+    )
+    .respond_to(&req)
+    .await
+}
+
+macro_rules! app {
+    ($factory:expr) => {
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new($factory))
+                .service(web::resource("/users").to(handler)),
+        )
+        .await
+    };
+}
+
+fn visit() -> test::TestRequest {
+    test::TestRequest::get()
+        .uri("/users")
+        .insert_header((X_INERTIA, "true"))
+}
+
+fn factory(dev_mode: bool, limit: SizeLimit) -> ResponseFactory {
+    let mut factory = ResponseFactory::new();
+    factory.set_dev_mode(dev_mode);
+    factory.set_size_limit(limit);
+    factory
+}
+
+#[actix_web::test]
+async fn test_pages_under_the_limit_are_not_reported() {
+    let app = app!(factory(true, SizeLimit::new(64 * 1024).report_header(true)));
+
+    let resp = test::call_service(&app, visit().to_request()).await;
+
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    assert!(!resp.headers().contains_key(X_INERTIA_DEBUG_PROP_SIZES));
+}
+
+#[actix_web::test]
+async fn test_dev_mode_reports_the_biggest_props() {
+    let app = app!(factory(true, SizeLimit::new(256).report_header(true)));
+
+    for req in [visit(), test::TestRequest::get().uri("/users")] {
+        let resp = test::call_service(&app, req.to_request()).await;
+
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(X_INERTIA_DEBUG_PROP_SIZES).unwrap(),
+            "users=1801, roles=18, title=7"
+        );
+    }
+}
+
+#[actix_web::test]
+async fn test_the_report_header_is_opt_in() {
+    let app = app!(factory(true, SizeLimit::new(256)));
+
+    let resp = test::call_service(&app, visit().to_request()).await;
+
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    assert!(!resp.headers().contains_key(X_INERTIA_DEBUG_PROP_SIZES));
+}
+
+#[actix_web::test]
+async fn test_production_warns_without_a_header() {
+    let limit = SizeLimit::new(256)
+        .report_header(true)
+        .on_oversized(Oversized::Warn);
+    let app = app!(factory(false, limit));
+
+    let resp = test::call_service(&app, visit().to_request()).await;
+
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    assert!(!resp.headers().contains_key(X_INERTIA_DEBUG_PROP_SIZES));
+}
+
+#[actix_web::test]
+async fn test_production_can_reject_oversized_pages() {
+    let limit = SizeLimit::new(256).on_oversized(Oversized::Reject);
+    let app = app!(factory(false, limit));
+
+    let resp = test::call_service(&app, visit().to_request()).await;
+
+    assert_eq!(resp.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "testing")]
+
+use actix_inertia::testing::{assert_inertia, InertiaTestRequest};
+use actix_inertia::{ResponseFactory, VersionMiddleware};
+use actix_web::{test, web, App, HttpRequest};
+use std::{env, fs};
+
+async fn users(req: HttpRequest, data: web::Data<ResponseFactory>) -> impl actix_web::Responder {
+    let users: Vec<_> = (0..10)
+        .map(|i| serde_json::json!({"id": i, "name": format!("User {i}")}))
+        .collect();
+    let inertia = data.render(
+        "Users/Index",
+        serde_json::json!({
+            "users": users,
+            "user": {"name": "Ada"},
+            "stats": {"total": 10},
+        }),
+        req.uri().to_string().as_str(),
+    );
+    inertia.into_response(&req).await
+}
+
+fn factory() -> ResponseFactory {
+    let mut factory = ResponseFactory::new();
+    factory.set_version(|| "1.0".to_string());
+    factory
+}
+
+#[actix_web::test]
+async fn test_json_responses_can_be_asserted() {
+    let app = test::init_service(
+        App::new()
+            .wrap(VersionMiddleware::new("1.0".to_string()))
+            .app_data(web::Data::new(factory()))
+            .service(web::resource("/users").to(users)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/users")
+        .inertia("1.0")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_inertia(resp)
+        .await
+        .component("Users/Index")
+        .url("/users")
+        .version("1.0")
+        .has("users", 10)
+        .where_("user.name", "Ada")
+        .where_("users.3.id", 3)
+        .missing("secret");
+}
+
+#[actix_web::test]
+async fn test_partial_requests_can_be_asserted() {
+    let app = test::init_service(
+        App::new()
+            .wrap(VersionMiddleware::new("1.0".to_string()))
+            .app_data(web::Data::new(factory()))
+            .service(web::resource("/users").to(users)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/users")
+        .inertia("1.0")
+        .partial("Users/Index", &["stats"])
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_inertia(resp)
+        .await
+        .exists("stats.total")
+        .missing("users")
+        .missing("user");
+}
+
+#[actix_web::test]
+async fn test_html_responses_can_be_asserted() {
+    let root_view = env::temp_dir().join(format!("testing_root_view_{}.html", std::process::id()));
+    fs::write(
+        &root_view,
+        "<div id=\"app\" data-page=\"{{DATA_PAGE}}\"></div>",
+    )
+    .unwrap();
+
+    let mut factory = factory();
+    factory.set_root_view(root_view.to_str().unwrap());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(factory))
+            .service(web::resource("/users").to(users)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/users").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_inertia(resp)
+        .await
+        .component("Users/Index")
+        .has("users", 10);
+
+    fs::remove_file(root_view).unwrap();
+}
+
+#[actix_web::test]
+#[should_panic(expected = "unexpected Inertia component")]
+async fn test_failed_assertions_panic() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(factory()))
+            .service(web::resource("/users").to(users)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/users")
+        .inertia("1.0")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_inertia(resp).await.component("Users/Show");
+}
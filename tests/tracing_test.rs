@@ -0,0 +1,200 @@
+#![cfg(feature = "tracing")]
+
+use actix_inertia::{Inertia, InertiaRequest, ResponseFactory};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+type SpanFields = (Id, String, HashMap<String, String>);
+
+/// The name and fields of every span, in the order they were created, and of every event.
+#[derive(Clone, Default)]
+struct Spans(
+    Arc<Mutex<Vec<SpanFields>>>,
+    Arc<Mutex<Vec<HashMap<String, String>>>>,
+);
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            format!("{value:?}").replace('"', ""),
+        );
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Spans {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        let mut fields = HashMap::new();
+        attrs.record(&mut Fields(&mut fields));
+        self.0
+            .lock()
+            .unwrap()
+            .push((id.clone(), attrs.metadata().name().to_string(), fields));
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = HashMap::new();
+        event.record(&mut Fields(&mut fields));
+        self.1.lock().unwrap().push(fields);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let mut spans = self.0.lock().unwrap();
+        if let Some((_, _, fields)) = spans.iter_mut().find(|(span, _, _)| span == id) {
+            values.record(&mut Fields(fields));
+        }
+    }
+}
+
+impl Spans {
+    fn event(&self, message: &str) -> Option<HashMap<String, String>> {
+        self.1
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|fields| fields.get("message").is_some_and(|m| m == message))
+            .cloned()
+    }
+
+    fn get(&self, name: &str) -> HashMap<String, String> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, span, _)| span == name)
+            .map(|(_, _, fields)| fields.clone())
+            .unwrap_or_else(|| panic!("no `{name}` span"))
+    }
+}
+
+fn request(builder: http::request::Builder) -> InertiaRequest {
+    let (parts, _) = builder.body(()).unwrap().into_parts();
+    InertiaRequest::from_parts(&parts)
+}
+
+#[test]
+fn test_render_spans_carry_component_and_payload_size() {
+    let spans = Spans::default();
+    let subscriber = tracing_subscriber::registry().with(spans.clone());
+
+    let page = tracing::subscriber::with_default(subscriber, || {
+        let request = request(
+            http::Request::get("/dashboard")
+                .header("X-Inertia", "true")
+                .header("X-Inertia-Partial-Component", "Dashboard")
+                .header("X-Inertia-Partial-Data", "stats"),
+        );
+        Inertia::new(
+            "Dashboard".to_string(),
+            json!({ "stats": 1, "users": 2 }),
+            "/dashboard".to_string(),
+        )
+        .render_page(&request, Some(&ResponseFactory::new()))
+        .unwrap()
+    });
+
+    let render = spans.get("inertia.render");
+    assert_eq!(render["component"], "Dashboard");
+    assert_eq!(render["inertia"], "true");
+    let actix_inertia::Rendered::Json(page) = page else {
+        panic!("expected a page object");
+    };
+    assert_eq!(render["payload_size"], page.len().to_string());
+
+    let filter = spans.get("inertia.filter_props");
+    assert_eq!(filter["props"], "2");
+    assert_eq!(filter["filtered"], "1");
+    spans.get("inertia.serialize_props");
+}
+
+#[test]
+fn test_version_conflicts_get_a_span() {
+    let spans = Spans::default();
+    let subscriber = tracing_subscriber::registry().with(spans.clone());
+    let mut factory = ResponseFactory::new();
+    factory.add_root_view("admin", actix_inertia::RootView::new("admin").version("2"));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let request = request(
+            http::Request::get("/admin")
+                .header("X-Inertia", "true")
+                .header("X-Inertia-Version", "1"),
+        );
+        Inertia::new("Admin".to_string(), json!({}), "/admin".to_string())
+            .root_view("admin")
+            .render_page(&request, Some(&factory))
+            .unwrap()
+    });
+
+    let conflict = spans.get("inertia.version_conflict");
+    assert_eq!(conflict["url"], "/admin");
+    assert_eq!(conflict["client_version"], "1");
+}
+
+#[cfg(feature = "actix")]
+#[actix_web::test]
+async fn test_request_events_redact_sensitive_headers() {
+    use actix_web::{test, web, App};
+
+    let spans = Spans::default();
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(spans.clone()));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .wrap(actix_inertia::VersionMiddleware::new("1".to_string()))
+            .service(web::resource("/").to(actix_inertia::example_handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header(("X-Inertia", "true"))
+        .insert_header(("X-Inertia-Version", "1"))
+        .insert_header(("Cookie", "session=secret"))
+        .insert_header(("Authorization", "Bearer secret"))
+        .to_request();
+    test::call_service(&app, req).await;
+
+    let event = spans.event("checking asset version").unwrap();
+    assert!(event["headers"].contains("cookie: [redacted]"));
+    assert!(event["headers"].contains("authorization: [redacted]"));
+    assert!(event["headers"].contains("x-inertia-version: 1"));
+    assert!(!event["headers"].contains("secret"));
+}
+
+#[test]
+fn test_props_are_only_logged_when_enabled() {
+    let render = |factory: &ResponseFactory| {
+        let spans = Spans::default();
+        tracing::subscriber::with_default(
+            tracing_subscriber::registry().with(spans.clone()),
+            || {
+                Inertia::new(
+                    "Account".to_string(),
+                    json!({ "email": "ada@example.com" }),
+                    "/account".to_string(),
+                )
+                .render_page(&request(http::Request::get("/account")), Some(factory))
+                .unwrap()
+            },
+        );
+        spans.event("page props")
+    };
+
+    let mut factory = ResponseFactory::new();
+    assert!(render(&factory).is_none());
+
+    factory.set_log_props(true);
+    let event = render(&factory).unwrap();
+    assert!(event["props"].contains("ada@example.com"));
+}
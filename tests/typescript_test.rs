@@ -0,0 +1,76 @@
+#![cfg(feature = "typescript")]
+
+use actix_inertia::typescript::PageTypes;
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+use ts_rs::TS;
+
+#[derive(Serialize, TS)]
+struct HelloProps {
+    message: String,
+}
+
+#[derive(Serialize, TS)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+#[derive(Serialize, TS)]
+struct UsersProps {
+    users: Vec<User>,
+}
+
+#[derive(Serialize, TS)]
+struct SharedProps {
+    auth: Option<User>,
+}
+
+fn page_types() -> PageTypes {
+    PageTypes::new()
+        .shared::<SharedProps>()
+        .page::<HelloProps>("Hello")
+        .page::<UsersProps>("Users/Index")
+}
+
+#[test]
+fn test_components_map_to_their_props() {
+    assert_eq!(
+        page_types().render(),
+        "// Generated by actix-inertia. Do not edit this file by hand.
+
+export type HelloProps = { message: string, };
+
+export type SharedProps = { auth: User | null, };
+
+export type User = { id: number, name: string, };
+
+export type UsersProps = { users: Array<User>, };
+
+export interface InertiaPages {
+  \"Hello\": HelloProps & SharedProps;
+  \"Users/Index\": UsersProps & SharedProps;
+}
+"
+    );
+}
+
+#[test]
+fn test_written_file_can_be_checked() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir = env::temp_dir().join(format!("page_types_{unique}"));
+    let path = dir.join("types/pages.d.ts");
+
+    page_types().write(&path).unwrap();
+    assert!(page_types().check(&path).is_ok());
+    assert!(PageTypes::new()
+        .page::<HelloProps>("Hello")
+        .check(&path)
+        .is_err());
+
+    fs::remove_dir_all(dir).unwrap();
+}
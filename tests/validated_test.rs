@@ -0,0 +1,222 @@
+#![cfg(all(feature = "validator", feature = "garde"))]
+
+use actix_inertia::precognition::{PRECOGNITION, PRECOGNITION_SUCCESS, PRECOGNITION_VALIDATE_ONLY};
+use actix_inertia::validated::Garde;
+use actix_inertia::validation::ERRORS_COOKIE;
+use actix_inertia::{ValidatedInertiaForm, X_INERTIA, X_INERTIA_ERROR_BAG};
+use actix_web::dev::ServiceResponse;
+use actix_web::{http, test, web, App, HttpResponse};
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+struct Address {
+    #[validate(length(min = 2, message = "The city is too short."))]
+    city: String,
+}
+
+#[derive(Deserialize, Validate)]
+struct SignupForm {
+    #[validate(email(message = "The email must be a valid email address."))]
+    email: String,
+    #[validate(length(min = 8))]
+    password: String,
+    #[validate(nested)]
+    address: Address,
+}
+
+#[derive(Deserialize, garde::Validate)]
+struct GardeForm {
+    #[garde(length(min = 3))]
+    name: String,
+}
+
+async fn signup(form: ValidatedInertiaForm<SignupForm>) -> HttpResponse {
+    HttpResponse::Ok().body(form.into_inner().email)
+}
+
+async fn garde_signup(form: ValidatedInertiaForm<GardeForm, Garde>) -> HttpResponse {
+    HttpResponse::Ok().body(form.into_inner().name)
+}
+
+fn flashed_errors(resp: &ServiceResponse) -> Value {
+    let cookie = resp
+        .response()
+        .cookies()
+        .find(|c| c.name() == ERRORS_COOKIE)
+        .expect("errors are flashed");
+    serde_json::from_str(cookie.value()).unwrap()
+}
+
+#[actix_web::test]
+async fn test_valid_form_reaches_the_handler() {
+    let app = test::init_service(App::new().service(web::resource("/signup").to(signup))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/signup")
+        .insert_header((X_INERTIA, "true"))
+        .set_json(serde_json::json!({
+            "email": "ada@example.com",
+            "password": "correct horse",
+            "address": {"city": "London"},
+        }))
+        .to_request();
+    let body = test::call_and_read_body(&app, req).await;
+
+    assert_eq!(body, "ada@example.com");
+}
+
+#[actix_web::test]
+async fn test_invalid_form_redirects_back_with_flat_errors() {
+    let app = test::init_service(App::new().service(web::resource("/signup").to(signup))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/signup")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((http::header::REFERER, "/signup"))
+        .set_json(serde_json::json!({
+            "email": "not-an-email",
+            "password": "short",
+            "address": {"city": "L"},
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::SEE_OTHER);
+    let errors = flashed_errors(&resp);
+    assert_eq!(errors["email"], "The email must be a valid email address.");
+    assert_eq!(errors["password"], "The password field is invalid.");
+    assert_eq!(errors["address.city"], "The city is too short.");
+}
+
+#[actix_web::test]
+async fn test_errors_are_nested_under_the_error_bag() {
+    let app = test::init_service(App::new().service(web::resource("/signup").to(signup))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/signup")
+        .insert_header((X_INERTIA, "true"))
+        .insert_header((X_INERTIA_ERROR_BAG, "createUser"))
+        .set_json(serde_json::json!({
+            "email": "not-an-email",
+            "password": "correct horse",
+            "address": {"city": "London"},
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    let errors = flashed_errors(&resp);
+    assert!(errors["createUser"]["email"].is_string());
+}
+
+#[actix_web::test]
+async fn test_garde_forms_are_validated() {
+    let app =
+        test::init_service(App::new().service(web::resource("/signup").to(garde_signup))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/signup")
+        .insert_header((X_INERTIA, "true"))
+        .set_json(serde_json::json!({"name": "Al"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::SEE_OTHER);
+    assert!(flashed_errors(&resp)["name"].is_string());
+}
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+async fn counted_signup(form: ValidatedInertiaForm<SignupForm>) -> HttpResponse {
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    HttpResponse::Ok().body(form.into_inner().email)
+}
+
+#[actix_web::test]
+async fn test_precognition_requests_never_reach_the_handler() {
+    let app =
+        test::init_service(App::new().service(web::resource("/signup").to(counted_signup))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/signup")
+        .insert_header((PRECOGNITION, "true"))
+        .set_json(serde_json::json!({
+            "email": "ada@example.com",
+            "password": "correct horse",
+            "address": {"city": "London"},
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::NO_CONTENT);
+    assert_eq!(resp.headers().get(PRECOGNITION_SUCCESS).unwrap(), "true");
+    assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+}
+
+#[actix_web::test]
+async fn test_precognition_reports_errors_with_422() {
+    let app = test::init_service(App::new().service(web::resource("/signup").to(signup))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/signup")
+        .insert_header((PRECOGNITION, "true"))
+        .set_json(serde_json::json!({
+            "email": "not-an-email",
+            "password": "short",
+            "address": {"city": "London"},
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(resp.headers().get(PRECOGNITION).unwrap(), "true");
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(
+        body["errors"]["email"][0],
+        "The email must be a valid email address."
+    );
+    assert!(body["errors"]["password"].is_array());
+}
+
+#[actix_web::test]
+async fn test_precognition_only_validates_requested_fields() {
+    let app = test::init_service(App::new().service(web::resource("/signup").to(signup))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/signup")
+        .insert_header((PRECOGNITION, "true"))
+        .insert_header((PRECOGNITION_VALIDATE_ONLY, "email"))
+        .set_json(serde_json::json!({
+            "email": "ada@example.com",
+            "password": "short",
+            "address": {"city": "London"},
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::NO_CONTENT);
+}
+
+#[actix_web::test]
+async fn test_precognition_reports_parse_errors_outside_the_requested_fields() {
+    let app = test::init_service(App::new().service(web::resource("/signup").to(signup))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/signup")
+        .insert_header((PRECOGNITION, "true"))
+        .insert_header((PRECOGNITION_VALIDATE_ONLY, "email"))
+        .set_json(serde_json::json!({
+            "email": "not-an-email",
+            "password": "correct horse",
+            "address": "London",
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), http::StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(resp.headers().get(PRECOGNITION_SUCCESS).is_none());
+    let body: Value = test::read_body_json(resp).await;
+    assert!(body["errors"]["address"].is_array());
+}
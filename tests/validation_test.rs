@@ -0,0 +1,71 @@
+#![cfg(feature = "actix")]
+
+use actix_inertia::validation::{ValidationErrors, ERRORS_COOKIE};
+use actix_inertia::{
+    inertia_responder::InertiaResponder, InertiaRequest, ResponseFactory, X_INERTIA,
+};
+use actix_web::{http, test, web, App, HttpRequest, HttpResponse};
+use serde_json::{json, Value};
+
+async fn form(req: HttpRequest) -> impl actix_web::Responder {
+    InertiaResponder::new("Form", json!({}))
+        .respond_to(&req)
+        .await
+}
+
+async fn store(req: HttpRequest) -> HttpResponse {
+    ValidationErrors::from_iter([
+        ("name", "Pflichtfeld; bitte ausfüllen"),
+        ("discount", "Must be 0%, 50% or 100%"),
+    ])
+    .redirect_back(&req)
+}
+
+#[actix_web::test]
+async fn test_flashed_errors_survive_the_cookie_round_trip() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ResponseFactory::new()))
+            .service(
+                web::resource("/form")
+                    .route(web::get().to(form))
+                    .route(web::post().to(store)),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/form")
+        .insert_header((http::header::REFERER, "/form"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let set_cookie = resp
+        .headers()
+        .get(http::header::SET_COOKIE)
+        .unwrap()
+        .to_str()
+        .unwrap();
+    let cookie = set_cookie.split(';').next().unwrap().to_string();
+    assert!(cookie.starts_with(ERRORS_COOKIE));
+    assert!(cookie.is_ascii() && !cookie.contains(','));
+
+    let expected = json!({
+        "name": "Pflichtfeld; bitte ausfüllen",
+        "discount": "Must be 0%, 50% or 100%",
+    });
+
+    let req = test::TestRequest::get()
+        .uri("/form")
+        .insert_header((http::header::COOKIE, cookie.as_str()))
+        .insert_header((X_INERTIA, "true"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["props"]["errors"], expected);
+
+    let (parts, _) = ::http::Request::get("/form")
+        .header("Cookie", format!("theme=dark; {cookie}"))
+        .body(())
+        .unwrap()
+        .into_parts();
+    assert_eq!(InertiaRequest::from_parts(&parts).errors, Some(expected));
+}